@@ -2,18 +2,6 @@ use anchor_lang::prelude::*;
 
 #[error_code]
 pub enum CustomError {
-    #[msg("Match is inactive")]
-    MatchInactive,
-    #[msg("Match is still active")]
-    MatchStillActive,
-    #[msg("Match is finalized")]
-    MatchFinalized,
-    #[msg("Match is already finalized")]
-    MatchAlreadyFinalized,
-    #[msg("Match not finalized")]
-    MatchNotFinalized,
-    #[msg("Registration is closed")]
-    RegistrationClosed,
     #[msg("Unauthorized")]
     Unauthorized,
     #[msg("Insufficient funds in the pool")]
@@ -26,4 +14,134 @@ pub enum CustomError {
     InsufficientBalance,
     #[msg("Token transfer error")]
     TokenTransferError,
-}
\ No newline at end of file
+    #[msg("Receiver must differ from the sender")]
+    InvalidReceiver,
+    #[msg("Prizes have already been allocated for claiming")]
+    PrizesAlreadyAllocated,
+    #[msg("Team entry already has a prize allocated")]
+    PrizeAlreadyAllocated,
+    #[msg("Prize amount must be greater than zero")]
+    InvalidPrizeAmount,
+    #[msg("Claim expiry must be in the future")]
+    InvalidClaimExpiry,
+    #[msg("Prize already claimed")]
+    PrizeAlreadyClaimed,
+    #[msg("Claim window has expired")]
+    ClaimExpired,
+    #[msg("Allocated prizes are still unclaimed")]
+    ClaimsOutstanding,
+    #[msg("No prizes have been allocated")]
+    NoPrizeAllocations,
+    #[msg("Invalid Merkle root")]
+    InvalidMerkleRoot,
+    #[msg("Invalid Merkle proof")]
+    InvalidMerkleProof,
+    #[msg("Fee must not exceed 10000 basis points")]
+    InvalidFeeBps,
+    #[msg("Invalid treasury token account")]
+    InvalidTreasury,
+    #[msg("Operator already exists")]
+    OperatorAlreadyExists,
+    #[msg("Operator not found")]
+    OperatorNotFound,
+    #[msg("Maximum number of operators reached")]
+    TooManyOperators,
+    #[msg("Token account mint does not match the pool mint")]
+    InvalidMint,
+    #[msg("Token account is not owned by the expected wallet")]
+    InvalidTokenOwner,
+    #[msg("Entry fee must be greater than zero")]
+    InvalidEntryFee,
+    #[msg("Entry limits must be greater than zero")]
+    InvalidEntryLimits,
+    #[msg("Number of entries must be greater than zero")]
+    InvalidEntryCount,
+    #[msg("Maximum entries per user exceeded")]
+    EntryLimitExceeded,
+    #[msg("Contest is full")]
+    ContestFull,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+    #[msg("Team index must be the user's next unused entry")]
+    InvalidTeamIndex,
+    #[msg("Team entry not found")]
+    TeamEntryNotFound,
+    #[msg("No prize to claim")]
+    NoPrizeToClaim,
+    #[msg("Team already revealed")]
+    TeamAlreadyRevealed,
+    #[msg("Revealed team does not match its commitment")]
+    InvalidReveal,
+    #[msg("Invalid team lineup")]
+    InvalidLineup,
+    #[msg("Team was never revealed")]
+    TeamNotRevealed,
+    #[msg("Scores have already been finalized")]
+    ScoresFinalized,
+    #[msg("Scores are not final yet")]
+    ScoresNotFinal,
+    #[msg("Too many players in the score sheet")]
+    TooManyPlayers,
+    #[msg("Team has already been scored")]
+    TeamAlreadyScored,
+    #[msg("Team has not been scored")]
+    TeamNotScored,
+    #[msg("Invalid prize table")]
+    InvalidPrizeTable,
+    #[msg("Leaderboard must include every revealed team")]
+    LeaderboardIncomplete,
+    #[msg("Leaderboard is not in ranking order")]
+    InvalidLeaderboardOrder,
+    #[msg("Too many teams to settle the leaderboard in one transaction")]
+    LeaderboardTooLarge,
+    #[msg("Prizes were settled from the leaderboard and cannot be set manually")]
+    LeaderboardSettled,
+    #[msg("Token account does not match the pool's dust sink")]
+    InvalidDustAccount,
+    #[msg("Page does not continue the prize distribution")]
+    InvalidDistributionCursor,
+    #[msg("Prize distribution is in progress")]
+    DistributionInProgress,
+    #[msg("Winner listed more than once")]
+    DuplicateWinner,
+    #[msg("Entries with a committed team cannot be withdrawn")]
+    EntriesInUse,
+    #[msg("Not allowed in the match's current status")]
+    InvalidMatchStatus,
+    #[msg("Invalid match status transition")]
+    InvalidStatusTransition,
+    #[msg("Results have already been posted")]
+    ResultsAlreadyPosted,
+    #[msg("Results have not been posted")]
+    ResultsNotPosted,
+    #[msg("Results hash does not match the proposed payouts")]
+    ResultsMismatch,
+    #[msg("Challenge window has closed")]
+    ChallengeWindowClosed,
+    #[msg("Payouts are locked until the challenge window closes without open disputes")]
+    PayoutsLocked,
+    #[msg("Prizes have already been paid against these results")]
+    PayoutsStarted,
+    #[msg("Invalid challenge period")]
+    InvalidChallengePeriod,
+    #[msg("Only the pool's sponsor can do this")]
+    InvalidSponsor,
+    #[msg("Guaranteed prize pool is not fully funded")]
+    GuaranteeNotFunded,
+    #[msg("Not enough participants")]
+    NotEnoughParticipants,
+    #[msg("Invalid match id")]
+    InvalidMatchId,
+    #[msg("Invalid registration end time")]
+    InvalidRegistrationEndTime,
+    #[msg("Invalid match start time")]
+    InvalidMatchStartTime,
+    #[msg("Contest is not denominated in SOL")]
+    NotNativeMint,
+    #[msg("Invalid entry fee range")]
+    InvalidEntryFeeRange,
+    #[msg("Entry fee outside the range allowed for this mint")]
+    EntryFeeOutOfRange,
+    #[msg("Program is paused")]
+    ProgramPaused,
+}
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::state::MatchStatus;
+
 #[event]
 pub struct DepositEvent {
     pub user: Pubkey,
@@ -7,15 +9,253 @@ pub struct DepositEvent {
     pub amount: u64,
 }
 
+#[event]
+pub struct ContestEnteredEvent {
+    pub user: Pubkey,
+    pub match_id: String,
+    pub entries: u32,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TeamCommittedEvent {
+    pub user: Pubkey,
+    pub match_id: String,
+    pub team_index: u16,
+    pub commitment: [u8; 32],
+}
+
+#[event]
+pub struct TeamRevealedEvent {
+    pub user: Pubkey,
+    pub match_id: String,
+    pub team_index: u16,
+    pub captain: u32,
+    pub vice_captain: u32,
+    pub players: [u32; 11],
+}
+
 #[event]
 pub struct MatchEndedEvent {
     pub match_id: String,
     pub total_deposited: u64,
 }
 
+#[event]
+pub struct FeeCollectedEvent {
+    pub match_id: String,
+    pub treasury: Pubkey,
+    pub amount: u64,
+}
+
 #[event]
 pub struct PrizeDistributedEvent {
     pub user: Pubkey,
     pub match_id: String,
+    pub team_index: u16,
+    pub amount: u64,
+}
+
+#[event]
+pub struct MatchCancelledEvent {
+    pub match_id: String,
+    pub total_deposited: u64,
+}
+
+#[event]
+pub struct WithdrawEvent {
+    pub user: Pubkey,
+    pub match_id: String,
+    pub entries: u32,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RefundEvent {
+    pub user: Pubkey,
+    pub match_id: String,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PrizeClaimedEvent {
+    pub user: Pubkey,
+    pub match_id: String,
+    pub team_index: Option<u16>,
     pub amount: u64,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct OperatorAddedEvent {
+    pub operator: Pubkey,
+}
+
+#[event]
+pub struct OperatorRemovedEvent {
+    pub operator: Pubkey,
+}
+
+#[event]
+pub struct TreasuryUpdatedEvent {
+    pub treasury: Pubkey,
+}
+
+#[event]
+pub struct SuperAdminTransferredEvent {
+    pub previous_super_admin: Pubkey,
+    pub new_super_admin: Pubkey,
+}
+
+#[event]
+pub struct OracleUpdatedEvent {
+    pub oracle: Pubkey,
+}
+
+#[event]
+pub struct ScoresSubmittedEvent {
+    pub match_id: String,
+    pub players: u32,
+    pub is_final: bool,
+}
+
+#[event]
+pub struct TeamScoredEvent {
+    pub user: Pubkey,
+    pub match_id: String,
+    pub team_index: u16,
+    pub score: i64,
+}
+
+#[event]
+pub struct LeaderboardSettledEvent {
+    pub match_id: String,
+    pub teams: u32,
+    pub total_allocated: u64,
+}
+
+#[event]
+pub struct DustSweptEvent {
+    pub match_id: String,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct DistributionProgressEvent {
+    pub match_id: String,
+    pub distribution_cursor: u32,
+    pub winner_count: u32,
+    pub distributed_so_far: u64,
+}
+
+#[event]
+pub struct MatchStatusChangedEvent {
+    pub match_id: String,
+    pub from: MatchStatus,
+    pub to: MatchStatus,
+}
+
+#[event]
+pub struct ChallengePeriodUpdatedEvent {
+    pub challenge_period: i64,
+}
+
+#[event]
+pub struct ResultsPostedEvent {
+    pub match_id: String,
+    pub results_hash: [u8; 32],
+    pub winner_count: u32,
+    pub challenge_end_time: i64,
+}
+
+#[event]
+pub struct DisputeRaisedEvent {
+    pub user: Pubkey,
+    pub match_id: String,
+    pub bond: u64,
+    pub reason: [u8; 32],
+}
+
+#[event]
+pub struct DisputeResolvedEvent {
+    pub user: Pubkey,
+    pub match_id: String,
+    pub upheld: bool,
+    pub bond: u64,
+}
+
+#[event]
+pub struct ResultsVoidedEvent {
+    pub match_id: String,
+    pub distribution_cursor: u32,
+}
+
+#[event]
+pub struct SponsorTopUpEvent {
+    pub sponsor: Pubkey,
+    pub match_id: String,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SponsorRefundEvent {
+    pub sponsor: Pubkey,
+    pub match_id: String,
+    pub amount: u64,
+}
+
+#[event]
+pub struct GuaranteedAmountUpdatedEvent {
+    pub match_id: String,
+    pub guaranteed_amount: u64,
+    pub sponsor: Pubkey,
+}
+
+#[event]
+pub struct RegistrationFinalizedEvent {
+    pub match_id: String,
+    pub participant_count: u32,
+    pub cancelled: bool,
+}
+
+#[event]
+pub struct ScheduleUpdatedEvent {
+    pub match_id: String,
+    pub registration_end_time: i64,
+    pub match_start_time: i64,
+}
+
+#[event]
+pub struct AllowedMintUpdatedEvent {
+    pub mint: Pubkey,
+    pub min_entry_fee: u64,
+    pub max_entry_fee: u64,
+}
+
+#[event]
+pub struct AllowedMintRemovedEvent {
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct GuardianUpdatedEvent {
+    pub guardian: Pubkey,
+}
+
+#[event]
+pub struct ProgramPausedEvent {
+    pub paused: bool,
+    pub exits_when_paused: bool,
+}
+
+#[event]
+pub struct PoolPausedEvent {
+    pub match_id: String,
+    pub paused: bool,
+}
+
+#[event]
+pub struct UnallocatedSweptEvent {
+    pub match_id: String,
+    pub amount: u64,
+}
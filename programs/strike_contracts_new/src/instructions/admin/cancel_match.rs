@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, CustomError, MatchCancelledEvent, MatchPool, MatchStatus};
+
+pub fn process_cancel_match(ctx: Context<CancelMatch>) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+
+    // Once the match is ended the fee is gone, so refunds could no longer be in full.
+    // Deposits stay in the pool until each user is refunded.
+    match_pool.transition_to(MatchStatus::Cancelled)?;
+
+    emit!(MatchCancelledEvent {
+        match_id: match_pool.match_id.clone(),
+        total_deposited: match_pool.total_deposited,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelMatch<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.is_operator(&admin.key()) @ CustomError::Unauthorized,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
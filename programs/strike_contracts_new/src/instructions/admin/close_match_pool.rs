@@ -1,13 +1,69 @@
 use anchor_lang::prelude::*;
 
-use anchor_spl::token::{Token, TokenAccount};
+use anchor_spl::token_interface::{close_account, CloseAccount, Mint, TokenAccount, TokenInterface};
 
-use crate::{CustomError, MatchPool};
+use crate::utils::transfer_from_pool;
+use crate::{Config, CustomError, DustSweptEvent, MatchPool, MatchStatus};
 
+// Closes the pool token account once depositors and sponsors have been paid
+// back. Anything still in it, like dust rolled over into a cancelled pool, goes
+// to the treasury first. The match pool itself stays, in `Closed` status, so
+// its match id can never be initialized again and inherit the deposits, team
+// entries, scores and claim receipts seeded by it.
 pub fn process_close_match_pool(ctx: Context<CloseMatchPool>) -> Result<()> {
-    let match_pool = &ctx.accounts.match_pool;
+    let match_pool = &mut ctx.accounts.match_pool;
 
-    require!(match_pool.is_finalized, CustomError::MatchNotFinalized);
+    let refunds_owed = match match_pool.status {
+        MatchStatus::Cancelled => match_pool.total_deposited,
+        _ => 0,
+    };
+    require!(
+        refunds_owed == 0 && match_pool.unused_sponsorship() == 0,
+        CustomError::PoolNotEmpty
+    );
+
+    match_pool.transition_to(MatchStatus::Closed)?;
+
+    let leftover = ctx.accounts.pool_token_account.amount;
+    if leftover > 0 {
+        transfer_from_pool(
+            match_pool,
+            &ctx.accounts.pool_token_account,
+            ctx.accounts.treasury_token_account.to_account_info(),
+            &ctx.accounts.token_mint,
+            &ctx.accounts.token_program,
+            leftover,
+        )?;
+
+        emit!(DustSweptEvent {
+            match_id: match_pool.match_id.clone(),
+            destination: ctx.accounts.treasury_token_account.key(),
+            amount: leftover,
+        });
+    }
+
+    // The pool token account is owned by the token program, so it has to be
+    // closed through it. Token-2022 pools must have withheld fees harvested first.
+    let seeds = &[
+        b"match_pool",
+        match_pool.match_id.as_bytes(),
+        &[match_pool.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let close_cpi_accounts = CloseAccount {
+        account: ctx.accounts.pool_token_account.to_account_info(),
+        destination: ctx.accounts.admin.to_account_info(),
+        authority: match_pool.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        close_cpi_accounts,
+        signer,
+    );
+
+    close_account(cpi_ctx)?;
 
     Ok(())
 }
@@ -18,23 +74,33 @@ pub struct CloseMatchPool<'info> {
         mut,
         seeds = [b"match_pool", match_pool.match_id.as_bytes()],
         bump = match_pool.bump,
-        constraint = match_pool.admin == admin.key() @ CustomError::Unauthorized,
-        close = admin
     )]
     pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.is_operator(&admin.key()) @ CustomError::Unauthorized,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
     
     #[account(
         mut,
         seeds = [b"pool_token", match_pool.match_id.as_bytes()],
         bump = match_pool.token_bump,
-        constraint = pool_token_account.amount == 0 @ CustomError::PoolNotEmpty,
-        close = admin
     )]
-    pub pool_token_account: Account<'info, TokenAccount>,
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = match_pool.mint @ CustomError::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = match_pool.treasury @ CustomError::InvalidTreasury)]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub admin: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
\ No newline at end of file
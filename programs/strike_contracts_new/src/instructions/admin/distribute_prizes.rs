@@ -1,85 +1,244 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, transfer, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::{error::CustomError, MatchPool, PrizeDistributedEvent};
+use std::collections::HashSet;
 
+use crate::merkle::{verify_proof, winner_leaf_hash};
+use crate::utils::{collect_team_entries, dust_destination, transfer_from_pool, update_team_entry};
+use crate::{
+    error::CustomError, Config, DistributionProgressEvent, DustSink, DustSweptEvent, MatchPool,
+    MatchStatus, PrizeDistributedEvent, UnallocatedSweptEvent,
+};
+
+// Pays the winning teams, best first, the amounts the pool's prize table
+// assigns to their ranks. The leaderboard is paid in pages, each starting where
+// the previous one stopped, and every winner must come with a proof against the
+// results posted with `post_results`, which also fixed the number of winners.
+// A page that was already paid is a no-op, so resending it is safe. The last
+// page sweeps what is left so the pool ends empty, apart from prizes of winners
+// without a token account, which they claim themselves. Rounding dust goes to
+// the dust sink; when that is first place, the share the prize table never
+// handed out goes to the treasury instead. SOL prizes are always left for
+// `claim_prize`, since unwrapping them needs the winner's signature.
 pub fn process_distribute_prizes<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, DistributePrizes<'info>>,
-    prize_distributions: Vec<PrizeDistribution>,
+    start: u32,
+    winners: Vec<RankedTeam>,
+    proofs: Vec<Vec<[u8; 32]>>,
 ) -> Result<()> {
     let match_pool = &mut ctx.accounts.match_pool;
+    let clock = Clock::get()?;
 
-    require!(!match_pool.is_active, CustomError::MatchStillActive);
-    require!(!match_pool.is_finalized, CustomError::MatchAlreadyFinalized);
+    match_pool.check_status(&[MatchStatus::Scoring])?;
+    require!(match_pool.payouts_unlocked(clock.unix_timestamp), CustomError::PayoutsLocked);
+    require!(match_pool.winner_count > 0, CustomError::ResultsNotPosted);
+    require!(match_pool.is_guarantee_funded(), CustomError::GuaranteeNotFunded);
+    require!(!match_pool.prize_table.is_empty(), CustomError::InvalidPrizeTable);
+    require!(!winners.is_empty(), CustomError::InvalidDistributionCursor);
+    require!(proofs.len() == winners.len(), CustomError::ResultsMismatch);
 
-    let mut total_distribution: u64 = 0;
-    for distribution in prize_distributions.iter() {
-        total_distribution += distribution.amount;
-    }
+    let page_end = start.checked_add(winners.len() as u32)
+        .ok_or(error!(CustomError::ArithmeticOverflow))?;
 
-    require!(total_distribution <= match_pool.total_deposited, CustomError::InsufficientPoolFunds);
+    if page_end <= match_pool.distribution_cursor {
+        msg!("Page {}..{} was already distributed", start, page_end);
+        return Ok(());
+    }
 
     let remaining_accounts = ctx.remaining_accounts;
-    let pool_token_info = ctx.accounts.pool_token_account.to_account_info();
-    let match_pool_info = match_pool.to_account_info();
-    let token_program_info = ctx.accounts.token_program.to_account_info();
-
-    let seeds = &[
-        b"match_pool",
-        match_pool.match_id.as_bytes(),
-        &[match_pool.bump],
-    ];
-    let signer = &[&seeds[..]];
+    let token_program_key = ctx.accounts.token_program.key();
+
+    // Prizes are paid per team, so every winner's team entry must be passed in as well
+    let team_entries = collect_team_entries(remaining_accounts, &match_pool.key(), ctx.program_id);
     
     // Process all token accounts first to map users to their token accounts
     let mut user_token_accounts = std::collections::HashMap::new();
     
     for account in remaining_accounts.iter() {
-        if account.owner == &token::ID {
+        if account.owner == &token_program_key {
             // Create a copy of the data that we can deserialize
             let data = account.data.borrow();
             if let Ok(token_account) = TokenAccount::try_deserialize(&mut &data[..]) {
+                require!(token_account.mint == match_pool.mint, CustomError::InvalidMint);
                 user_token_accounts.insert(token_account.owner, account);
             }
         }
     }
     
+    // Strict mode fails the page when a winner's token account is missing instead
+    // of leaving the prize to be claimed
+    if match_pool.strict_distribution {
+        let mut seen_teams = HashSet::new();
+        for winner in winners.iter() {
+            require!(seen_teams.insert((winner.user, winner.team_index)), CustomError::DuplicateWinner);
+            require!(
+                team_entries.contains_key(&(winner.user, winner.team_index)),
+                CustomError::TeamEntryNotFound
+            );
+            require!(
+                match_pool.is_native() || user_token_accounts.contains_key(&winner.user),
+                CustomError::WinnerAccountNotFound
+            );
+        }
+    }
+
+    require!(start == match_pool.distribution_cursor, CustomError::InvalidDistributionCursor);
+    require!(page_end <= match_pool.winner_count, CustomError::InvalidDistributionCursor);
+
+    // Nothing is paid that the posted results do not list at that position
+    for (offset, (winner, proof)) in winners.iter().zip(proofs.iter()).enumerate() {
+        require!(
+            verify_proof(proof, &match_pool.results_hash, winner.leaf(start + offset as u32)),
+            CustomError::ResultsMismatch
+        );
+    }
+
+    let ranks: Vec<u16> = winners.iter().map(|winner| winner.rank).collect();
+    require!(
+        MatchPool::is_valid_ranking(start as usize, &ranks),
+        CustomError::InvalidLeaderboardOrder
+    );
+
+    let amounts = match_pool.prize_amounts(start as usize, &ranks);
+
+    if start == 0 {
+        match_pool.first_place = winners[0].user;
+    }
+
     // Now process the prizes without borrowing the same account multiple times
-    for prize in prize_distributions {
-        if prize.amount <= 0 {
+    let mut distributed = match_pool.distributed_so_far;
+
+    for (winner, amount) in winners.into_iter().zip(amounts) {
+        if amount == 0 {
             continue;
         }
 
-        let winner_key = prize.user;
-        
-        if let Some(winner_account) = user_token_accounts.get(&winner_key) {
+        let winner_key = winner.user;
+        let team_entry_info = team_entries.get(&(winner_key, winner.team_index))
+            .ok_or(error!(CustomError::TeamEntryNotFound))?;
+        let winner_account = user_token_accounts.get(&winner_key)
+            .filter(|_| !match_pool.is_native());
+
+        // A prize that cannot be pushed is recorded for the winner to pull with `claim_prize`
+        update_team_entry(team_entry_info, |team_entry| {
+            require!(team_entry.is_revealed, CustomError::TeamNotRevealed);
+            require!(!team_entry.is_claimed, CustomError::PrizeAlreadyClaimed);
+            require!(team_entry.prize_amount == 0, CustomError::PrizeAlreadyAllocated);
+            team_entry.prize_amount = amount;
+            team_entry.is_claimed = winner_account.is_some();
+            Ok(())
+        })?;
+
+        if let Some(winner_account) = winner_account {
             // Found the right account, transfer tokens
-            let transfer_cpi_accounts = Transfer {
-                from: pool_token_info.clone(),
-                to: (*winner_account).clone(),
-                authority: match_pool_info.clone(),
-            };
-            
-            let cpi_ctx = CpiContext::new_with_signer(
-                token_program_info.clone(),
-                transfer_cpi_accounts,
-                signer,
-            );
-            
-            transfer(cpi_ctx, prize.amount)?;
+            transfer_from_pool(
+                match_pool,
+                &ctx.accounts.pool_token_account,
+                (*winner_account).clone(),
+                &ctx.accounts.token_mint,
+                &ctx.accounts.token_program,
+                amount,
+            )?;
+
+            distributed = distributed.checked_add(amount)
+                .ok_or(error!(CustomError::ArithmeticOverflow))?;
             
             emit!(PrizeDistributedEvent {
                 user: winner_key,
                 match_id: match_pool.match_id.clone(),
-                amount: prize.amount,
+                team_index: winner.team_index,
+                amount,
             });
         } else {
-            msg!("Winner account not found for {}", winner_key);
-            // Skip if account not found
+            msg!("Prize for {} left to claim", winner_key);
+            match_pool.total_allocated = match_pool.total_allocated.checked_add(amount)
+                .ok_or(error!(CustomError::ArithmeticOverflow))?;
         }
     }
 
-    match_pool.is_finalized = true;
+    match_pool.distribution_cursor = page_end;
+    match_pool.distributed_so_far = distributed;
+
+    emit!(DistributionProgressEvent {
+        match_id: match_pool.match_id.clone(),
+        distribution_cursor: match_pool.distribution_cursor,
+        winner_count: match_pool.winner_count,
+        distributed_so_far: match_pool.distributed_so_far,
+    });
+
+    if match_pool.distribution_cursor < match_pool.winner_count {
+        return Ok(());
+    }
+
+    let sweep_token_account = &ctx.accounts.sweep_token_account;
+    require!(
+        match_pool.is_dust_account(&sweep_token_account.key(), &sweep_token_account.owner),
+        CustomError::InvalidDustAccount
+    );
+
+    ctx.accounts.pool_token_account.reload()?;
+    // Unused sponsor funds stay behind for `refund_sponsor`, unclaimed prizes for their winners
+    let unclaimed = match_pool.total_allocated - match_pool.total_claimed;
+    let leftover = ctx.accounts.pool_token_account.amount
+        .saturating_sub(match_pool.unused_sponsorship())
+        .saturating_sub(unclaimed);
+
+    let dust = match match_pool.dust_sink {
+        DustSink::FirstPlace => match_pool.table_pot(match_pool.winner_count as usize)
+            .saturating_sub(match_pool.distributed_so_far + match_pool.total_allocated)
+            .min(leftover),
+        _ => leftover,
+    };
+    let unallocated = leftover - dust;
+
+    if dust > 0 {
+        let destination = dust_destination(
+            match_pool,
+            ctx.accounts.rollover_pool.as_mut(),
+            &ctx.accounts.sweep_token_account,
+            &ctx.accounts.treasury_token_account,
+            dust,
+        )?;
+
+        transfer_from_pool(
+            match_pool,
+            &ctx.accounts.pool_token_account,
+            destination.to_account_info(),
+            &ctx.accounts.token_mint,
+            &ctx.accounts.token_program,
+            dust,
+        )?;
+
+        emit!(DustSweptEvent {
+            match_id: match_pool.match_id.clone(),
+            destination: destination.key(),
+            amount: dust,
+        });
+    }
+
+    if unallocated > 0 {
+        transfer_from_pool(
+            match_pool,
+            &ctx.accounts.pool_token_account,
+            ctx.accounts.treasury_token_account.to_account_info(),
+            &ctx.accounts.token_mint,
+            &ctx.accounts.token_program,
+            unallocated,
+        )?;
+
+        emit!(UnallocatedSweptEvent {
+            match_id: match_pool.match_id.clone(),
+            amount: unallocated,
+        });
+    }
+
+    // Prizes left to claim keep the pool in scoring until `finalize_prize_claims`
+    if unclaimed > 0 {
+        match_pool.claim_expiry = clock.unix_timestamp + MatchPool::SETTLEMENT_CLAIM_WINDOW;
+        return Ok(());
+    }
+
+    match_pool.transition_to(MatchStatus::Settled)?;
 
     Ok(())
 }
@@ -90,26 +249,68 @@ pub struct DistributePrizes<'info> {
         mut,
         seeds = [b"match_pool", match_pool.match_id.as_bytes()],
         bump = match_pool.bump,
-        constraint = match_pool.admin == admin.key() @ CustomError::Unauthorized,
     )]
     pub match_pool: Account<'info, MatchPool>,
 
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.is_operator(&admin.key()) @ CustomError::Unauthorized,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
     #[account(
         mut,
         seeds = [b"pool_token", match_pool.match_id.as_bytes()],
         bump = match_pool.token_bump,
     )]
-    pub pool_token_account: Account<'info, TokenAccount>,
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = match_pool.mint @ CustomError::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Only checked on the last page, where the dust is swept
+    #[account(
+        mut,
+        constraint = sweep_token_account.mint == match_pool.mint @ CustomError::InvalidMint,
+    )]
+    pub sweep_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Receives the share the prize table never handed out when dust goes to
+    /// first place, and dust meant for a pool that has already started settling
+    #[account(mut, address = match_pool.treasury @ CustomError::InvalidTreasury)]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The pool the dust rolls over into, only needed with a rollover dust sink
+    #[account(mut)]
+    pub rollover_pool: Option<Account<'info, MatchPool>>,
 
     #[account(mut)]
     pub admin: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
+/// A winning team and its competition rank; tied teams share a rank
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RankedTeam {
+    pub user: Pubkey,
+    pub team_index: u16,
+    pub rank: u16,
+}
+
+impl RankedTeam {
+    /// This winner's leaf in the posted results when listed at `position`
+    pub fn leaf(&self, position: u32) -> [u8; 32] {
+        winner_leaf_hash(position, &self.user, self.team_index, self.rank)
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct PrizeDistribution {
     pub user: Pubkey,
+    pub team_index: u16,
     pub amount: u64,
 }
\ No newline at end of file
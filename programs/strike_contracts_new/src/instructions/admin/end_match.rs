@@ -1,14 +1,38 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::{CustomError, MatchEndedEvent, MatchPool};
+use crate::utils::transfer_from_pool;
+use crate::{Config, CustomError, FeeCollectedEvent, MatchEndedEvent, MatchPool, MatchStatus};
 
 pub fn process_end_match(ctx: Context<EndMatch>) -> Result<()> {
     let match_pool = &mut ctx.accounts.match_pool;
 
-    require!(match_pool.is_active, CustomError::MatchInactive);
-    require!(!match_pool.is_finalized, CustomError::MatchFinalized);
+    // Short contests are cancelled by `finalize_registration` instead
+    require!(match_pool.has_enough_participants(), CustomError::NotEnoughParticipants);
+    match_pool.transition_to(MatchStatus::Scoring)?;
 
-    match_pool.is_active = false;
+    // Take the platform fee up front so every settlement path sees the post-fee pot
+    let fee = (match_pool.total_deposited as u128 * match_pool.fee_bps as u128
+        / MatchPool::MAX_FEE_BPS as u128) as u64;
+
+    if fee > 0 {
+        transfer_from_pool(
+            match_pool,
+            &ctx.accounts.pool_token_account,
+            ctx.accounts.treasury_token_account.to_account_info(),
+            &ctx.accounts.token_mint,
+            &ctx.accounts.token_program,
+            fee,
+        )?;
+
+        match_pool.fee_collected = fee;
+
+        emit!(FeeCollectedEvent {
+            match_id: match_pool.match_id.clone(),
+            treasury: match_pool.treasury,
+            amount: fee,
+        });
+    }
 
     emit!(MatchEndedEvent {
         match_id: match_pool.match_id.clone(),
@@ -24,11 +48,35 @@ pub struct EndMatch<'info> {
         mut,
         seeds = [b"match_pool", match_pool.match_id.as_bytes()],
         bump = match_pool.bump,
-        constraint = match_pool.admin == admin.key() @ CustomError::Unauthorized,
     )]
     pub match_pool: Account<'info, MatchPool>,
 
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.is_operator(&admin.key()) @ CustomError::Unauthorized,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_token", match_pool.match_id.as_bytes()],
+        bump = match_pool.token_bump,
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = match_pool.mint @ CustomError::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        address = match_pool.treasury @ CustomError::InvalidTreasury,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
     pub admin: Signer<'info>,
 
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
\ No newline at end of file
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::utils::{dust_destination, transfer_from_pool};
+use crate::{Config, CustomError, DustSweptEvent, MatchPool, MatchStatus};
+
+// Finalizes a claim-based settlement once every allocation is claimed or the
+// claim window has expired. Whatever is left in the pool goes to the dust sink.
+pub fn process_finalize_prize_claims(ctx: Context<FinalizePrizeClaims>) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+    let clock = Clock::get()?;
+
+    match_pool.check_status(&[MatchStatus::Scoring])?;
+    require!(match_pool.payouts_unlocked(clock.unix_timestamp), CustomError::PayoutsLocked);
+    require!(match_pool.total_allocated > 0, CustomError::NoPrizeAllocations);
+    require!(
+        match_pool.total_claimed == match_pool.total_allocated
+            || clock.unix_timestamp >= match_pool.claim_expiry,
+        CustomError::ClaimsOutstanding
+    );
+
+    // Unused sponsor funds stay behind for `refund_sponsor`
+    let remaining = ctx.accounts.pool_token_account.amount
+        .saturating_sub(match_pool.unused_sponsorship());
+
+    if remaining > 0 {
+        let destination = dust_destination(
+            match_pool,
+            ctx.accounts.rollover_pool.as_mut(),
+            &ctx.accounts.sweep_token_account,
+            &ctx.accounts.treasury_token_account,
+            remaining,
+        )?;
+
+        transfer_from_pool(
+            match_pool,
+            &ctx.accounts.pool_token_account,
+            destination.to_account_info(),
+            &ctx.accounts.token_mint,
+            &ctx.accounts.token_program,
+            remaining,
+        )?;
+
+        emit!(DustSweptEvent {
+            match_id: match_pool.match_id.clone(),
+            destination: destination.key(),
+            amount: remaining,
+        });
+    }
+
+    match_pool.transition_to(MatchStatus::Settled)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinalizePrizeClaims<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.is_operator(&admin.key()) @ CustomError::Unauthorized,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_token", match_pool.match_id.as_bytes()],
+        bump = match_pool.token_bump,
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = match_pool.mint @ CustomError::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = match_pool.is_sweep_account(&sweep_token_account.key()) @ CustomError::InvalidDustAccount,
+        constraint = sweep_token_account.mint == match_pool.mint @ CustomError::InvalidMint,
+    )]
+    pub sweep_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Receives dust meant for a pool that has already started settling
+    #[account(mut, address = match_pool.treasury @ CustomError::InvalidTreasury)]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The pool the dust rolls over into, only needed with a rollover dust sink
+    #[account(mut)]
+    pub rollover_pool: Option<Account<'info, MatchPool>>,
+
+    pub admin: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
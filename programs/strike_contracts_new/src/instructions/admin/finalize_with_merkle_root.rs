@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, CustomError, MatchPool, MatchStatus};
+
+// Commits the root of a `(user, amount)` prize tree so winners can claim with
+// `claim_with_proof`. The tree is built off-chain with `crate::merkle::MerkleTree`.
+pub fn process_finalize_with_merkle_root(
+    ctx: Context<FinalizeWithMerkleRoot>,
+    merkle_root: [u8; 32],
+    total_amount: u64,
+    claim_expiry: i64,
+) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+    let clock = Clock::get()?;
+
+    match_pool.check_status(&[MatchStatus::Scoring])?;
+    require!(!match_pool.results_posted(), CustomError::ResultsAlreadyPosted);
+    require!(match_pool.is_guarantee_funded(), CustomError::GuaranteeNotFunded);
+    require!(match_pool.total_allocated == 0, CustomError::PrizesAlreadyAllocated);
+    require!(!match_pool.distribution_started(), CustomError::DistributionInProgress);
+    require!(merkle_root != [0u8; 32], CustomError::InvalidMerkleRoot);
+    require!(total_amount > 0, CustomError::InvalidPrizeAmount);
+    require!(total_amount <= match_pool.prize_pool(), CustomError::InsufficientPoolFunds);
+    require!(claim_expiry > clock.unix_timestamp, CustomError::InvalidClaimExpiry);
+
+    match_pool.merkle_root = merkle_root;
+    match_pool.total_allocated = total_amount;
+    match_pool.claim_expiry = claim_expiry;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinalizeWithMerkleRoot<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.is_operator(&admin.key()) @ CustomError::Unauthorized,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
@@ -1,19 +1,75 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{associated_token::AssociatedToken, token::{Mint, Token, TokenAccount}};
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
 
-use crate::state::MatchPool;
+use crate::{error::CustomError, state::{AllowedMint, Config, DustSink, MatchPool, MatchStatus}};
 
-pub fn process_initialize(ctx: Context<Initialize>, match_id: String, registration_end_time: i64) -> Result<()> {
+pub fn process_initialize(
+    ctx: Context<Initialize>,
+    match_id: String,
+    registration_end_time: i64,
+    match_start_time: i64,
+    fee_bps: u16,
+    entry_rules: EntryRules,
+) -> Result<()> {
     let match_pool = &mut ctx.accounts.match_pool;
     let admin = &ctx.accounts.admin;
+    let clock = Clock::get()?;
+
+    require!(
+        !match_id.is_empty() && match_id.len() <= MatchPool::MAX_MATCH_ID_LEN,
+        CustomError::InvalidMatchId
+    );
+    require!(registration_end_time > clock.unix_timestamp, CustomError::InvalidRegistrationEndTime);
+    require!(match_start_time >= registration_end_time, CustomError::InvalidMatchStartTime);
+
+    require!(fee_bps <= MatchPool::MAX_FEE_BPS, CustomError::InvalidFeeBps);
+    require!(entry_rules.entry_fee > 0, CustomError::InvalidEntryFee);
+    require!(
+        ctx.accounts.allowed_mint.accepts_entry_fee(entry_rules.entry_fee),
+        CustomError::EntryFeeOutOfRange
+    );
+    require!(
+        entry_rules.max_entries_per_user > 0
+            && entry_rules.max_participants > 0
+            && entry_rules.min_participants <= entry_rules.max_participants,
+        CustomError::InvalidEntryLimits
+    );
 
     // Initialize the match pool
     match_pool.admin = admin.key();
     match_pool.match_id = match_id;
     match_pool.registration_end_time = registration_end_time;
+    match_pool.match_start_time = match_start_time;
     match_pool.total_deposited = 0;
-    match_pool.is_active = true;
-    match_pool.is_finalized = false;
+    match_pool.fee_bps = fee_bps;
+    match_pool.fee_collected = 0;
+    match_pool.sponsor = Pubkey::default();
+    match_pool.sponsored_amount = 0;
+    match_pool.guaranteed_amount = 0;
+    match_pool.rollover_amount = 0;
+    match_pool.treasury = ctx.accounts.treasury_token_account.key();
+    match_pool.mint = ctx.accounts.token_mint.key();
+    match_pool.entry_fee = entry_rules.entry_fee;
+    match_pool.max_entries_per_user = entry_rules.max_entries_per_user;
+    match_pool.max_participants = entry_rules.max_participants;
+    match_pool.min_participants = entry_rules.min_participants;
+    match_pool.participant_count = 0;
+    match_pool.status = MatchStatus::Open;
+    match_pool.total_allocated = 0;
+    match_pool.total_claimed = 0;
+    match_pool.claim_expiry = 0;
+    match_pool.leaderboard_settled = false;
+    match_pool.prize_table = Vec::new();
+    match_pool.dust_sink = DustSink::Treasury;
+    match_pool.winner_count = 0;
+    match_pool.distribution_cursor = 0;
+    match_pool.distributed_so_far = 0;
+    match_pool.strict_distribution = true;
+    match_pool.results_hash = [0u8; 32];
+    match_pool.challenge_end_time = 0;
+    match_pool.open_disputes = 0;
+    match_pool.paused = false;
+    match_pool.merkle_root = [0u8; 32];
     match_pool.bump = ctx.bumps.match_pool;
     match_pool.token_bump = ctx.bumps.pool_token_account;
 
@@ -21,7 +77,7 @@ pub fn process_initialize(ctx: Context<Initialize>, match_id: String, registrati
 }
 
 #[derive(Accounts)]
-#[instruction(match_id: String, registration_end_time: i64)]
+#[instruction(match_id: String)]
 pub struct Initialize<'info> {
     #[account(
         init,
@@ -32,6 +88,14 @@ pub struct Initialize<'info> {
     )]
     pub match_pool: Account<'info, MatchPool>,
 
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.is_operator(&admin.key()) @ CustomError::Unauthorized,
+        constraint = !config.paused @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
     #[account(
         init,
         payer = admin,
@@ -39,17 +103,40 @@ pub struct Initialize<'info> {
         bump,
         token::mint = token_mint,
         token::authority = match_pool,
+        token::token_program = token_program,
     )]
-    pub pool_token_account: Account<'info, TokenAccount>,
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Only allowlisted mints can back a contest
+    #[account(
+        seeds = [b"allowed_mint", token_mint.key().as_ref()],
+        bump = allowed_mint.bump,
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+
+    #[account(
+        constraint = treasury_token_account.mint == token_mint.key() @ CustomError::InvalidTreasury,
+        constraint = treasury_token_account.owner == config.treasury @ CustomError::InvalidTreasury,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(mut)]
     pub admin: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct EntryRules {
+    pub entry_fee: u64,
+    pub max_entries_per_user: u32,
+    pub max_participants: u32,
+    /// Below this headcount at the deadline the contest is cancelled and refunded
+    pub min_participants: u32,
 }
\ No newline at end of file
@@ -5,4 +5,24 @@ mod end_match;
 pub use distribute_prizes::*;
 mod distribute_prizes;
 pub use close_match_pool::*;
-mod close_match_pool;
\ No newline at end of file
+mod close_match_pool;
+pub use cancel_match::*;
+mod cancel_match;
+pub use set_prize_allocations::*;
+mod set_prize_allocations;
+pub use finalize_prize_claims::*;
+mod finalize_prize_claims;
+pub use finalize_with_merkle_root::*;
+mod finalize_with_merkle_root;
+pub use set_prize_table::*;
+mod set_prize_table;
+pub use set_strict_distribution::*;
+mod set_strict_distribution;
+pub use post_results::*;
+mod post_results;
+pub use set_guaranteed_amount::*;
+mod set_guaranteed_amount;
+pub use update_schedule::*;
+mod update_schedule;
+pub use set_pool_paused::*;
+mod set_pool_paused;
\ No newline at end of file
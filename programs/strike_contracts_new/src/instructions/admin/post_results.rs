@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, CustomError, MatchPool, MatchStatus, ResultsPostedEvent};
+
+// Publishes the proposed payouts and opens the challenge window. With a Merkle
+// settlement `results_hash` is the committed root. For `distribute_prizes` it is
+// the root of a tree of `merkle::winner_leaf_hash` leaves over the `winner_count`
+// winners, and every page is checked against it. Claim-based settlements post
+// no winner count. Allocations on team entries are frozen from here on.
+pub fn process_post_results(
+    ctx: Context<PostResults>,
+    results_hash: [u8; 32],
+    winner_count: u32,
+) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+    let clock = Clock::get()?;
+
+    match_pool.check_status(&[MatchStatus::Scoring])?;
+    require!(!match_pool.results_posted(), CustomError::ResultsAlreadyPosted);
+    require!(match_pool.is_guarantee_funded(), CustomError::GuaranteeNotFunded);
+    require!(results_hash != [0u8; 32], CustomError::ResultsMismatch);
+
+    if match_pool.merkle_root != [0u8; 32] {
+        require!(results_hash == match_pool.merkle_root, CustomError::ResultsMismatch);
+    }
+
+    // Results must leave a way to settle, either pushed payouts or claims
+    require!(winner_count > 0 || match_pool.total_allocated > 0, CustomError::NoPrizeAllocations);
+    // A distribution that already paid some pages continues from its cursor
+    require!(
+        match_pool.distribution_cursor == 0 || winner_count > match_pool.distribution_cursor,
+        CustomError::InvalidDistributionCursor
+    );
+
+    // Pushed payouts and claim-based allocations are never mixed
+    if winner_count > 0 {
+        require!(match_pool.total_allocated == 0, CustomError::PrizesAlreadyAllocated);
+        require!(!match_pool.prize_table.is_empty(), CustomError::InvalidPrizeTable);
+    }
+
+    let challenge_end_time = clock.unix_timestamp + ctx.accounts.config.challenge_period;
+
+    // Winners of a claim-based settlement need time to claim once the window closes
+    if match_pool.total_allocated > 0 {
+        require!(match_pool.claim_expiry > challenge_end_time, CustomError::InvalidClaimExpiry);
+    }
+
+    match_pool.results_hash = results_hash;
+    match_pool.winner_count = winner_count;
+    match_pool.challenge_end_time = challenge_end_time;
+
+    emit!(ResultsPostedEvent {
+        match_id: match_pool.match_id.clone(),
+        results_hash,
+        winner_count,
+        challenge_end_time,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PostResults<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.is_operator(&admin.key()) @ CustomError::Unauthorized,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
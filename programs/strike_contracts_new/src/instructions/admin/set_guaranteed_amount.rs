@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, CustomError, GuaranteedAmountUpdatedEvent, MatchPool, MatchStatus};
+
+// Sets the prize pool promised regardless of entries and the sponsor backing it.
+// Any shortfall is covered from `sponsor_top_up` funds, and settlement is blocked
+// until it is. The sponsor cannot be swapped once they have funds in the pool.
+pub fn process_set_guaranteed_amount(
+    ctx: Context<SetGuaranteedAmount>,
+    guaranteed_amount: u64,
+    sponsor: Pubkey,
+) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+
+    match_pool.check_status(&[MatchStatus::Open])?;
+    require!(
+        match_pool.sponsored_amount == 0 || match_pool.sponsor == sponsor,
+        CustomError::InvalidSponsor
+    );
+
+    match_pool.guaranteed_amount = guaranteed_amount;
+    match_pool.sponsor = sponsor;
+
+    emit!(GuaranteedAmountUpdatedEvent {
+        match_id: match_pool.match_id.clone(),
+        guaranteed_amount,
+        sponsor,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetGuaranteedAmount<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.is_operator(&admin.key()) @ CustomError::Unauthorized,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
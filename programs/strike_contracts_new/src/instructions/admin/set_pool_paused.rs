@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, CustomError, MatchPool, PoolPausedEvent};
+
+// Pauses a single pool, with the same exits as the program-wide switch. Like the
+// program-wide switch it belongs to the guardian, so operators cannot lift it.
+pub fn process_set_pool_paused(ctx: Context<SetPoolPaused>, paused: bool) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+
+    match_pool.paused = paused;
+
+    emit!(PoolPausedEvent {
+        match_id: match_pool.match_id.clone(),
+        paused,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPoolPaused<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.can_pause(&guardian.key()) @ CustomError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub guardian: Signer<'info>,
+}
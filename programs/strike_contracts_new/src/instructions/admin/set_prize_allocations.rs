@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::utils::{collect_team_entries, update_team_entry};
+use crate::{Config, CustomError, MatchPool, MatchStatus, PrizeDistribution};
+
+// Records each winning team's prize on its team entry so the owner can pull it
+// with `claim_prize`. Can be called in several batches; the team entries are
+// passed in `remaining_accounts`. Allocating a team again replaces its prize and
+// an amount of zero removes it, so allocations can be corrected until the results
+// are posted, and again once an upheld dispute has voided them. Prizes computed
+// by `rank_and_settle` come from the scores and can never be overwritten here.
+pub fn process_set_prize_allocations<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, SetPrizeAllocations<'info>>,
+    allocations: Vec<PrizeDistribution>,
+    claim_expiry: i64,
+) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+    let clock = Clock::get()?;
+
+    match_pool.check_status(&[MatchStatus::Scoring])?;
+    require!(!match_pool.results_posted(), CustomError::ResultsAlreadyPosted);
+    require!(match_pool.is_guarantee_funded(), CustomError::GuaranteeNotFunded);
+    require!(match_pool.merkle_root == [0u8; 32], CustomError::PrizesAlreadyAllocated);
+    require!(!match_pool.leaderboard_settled, CustomError::LeaderboardSettled);
+    require!(!match_pool.distribution_started(), CustomError::DistributionInProgress);
+    require!(
+        claim_expiry > clock.unix_timestamp && claim_expiry >= match_pool.claim_expiry,
+        CustomError::InvalidClaimExpiry
+    );
+
+    let team_entries = collect_team_entries(ctx.remaining_accounts, &match_pool.key(), ctx.program_id);
+    let mut total_allocated = match_pool.total_allocated;
+
+    for allocation in allocations.iter() {
+        let team_entry_info = team_entries.get(&(allocation.user, allocation.team_index))
+            .ok_or(error!(CustomError::TeamEntryNotFound))?;
+
+        update_team_entry(team_entry_info, |team_entry| {
+            require!(team_entry.is_revealed, CustomError::TeamNotRevealed);
+            total_allocated = total_allocated.checked_sub(team_entry.prize_amount)
+                .and_then(|total| total.checked_add(allocation.amount))
+                .ok_or(error!(CustomError::ArithmeticOverflow))?;
+            team_entry.prize_amount = allocation.amount;
+            Ok(())
+        })?;
+    }
+
+    require!(total_allocated <= match_pool.prize_pool(), CustomError::InsufficientPoolFunds);
+
+    match_pool.total_allocated = total_allocated;
+    match_pool.claim_expiry = claim_expiry;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPrizeAllocations<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.is_operator(&admin.key()) @ CustomError::Unauthorized,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, CustomError, DustSink, MatchPool, MatchStatus, PrizeTier};
+
+// Stores the payout structure used by `distribute_prizes` and `rank_and_settle`,
+// e.g. 1st 40%, 2nd 20%, ranks 3-10 sharing 30%, along with where the dust goes.
+// Ranks outside every tier win nothing.
+pub fn process_set_prize_table(
+    ctx: Context<SetPrizeTable>,
+    prize_table: Vec<PrizeTier>,
+    dust_sink: DustSink,
+) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+
+    match_pool.check_status(&[MatchStatus::Open, MatchStatus::Locked, MatchStatus::Scoring])?;
+    require!(!match_pool.results_posted(), CustomError::ResultsAlreadyPosted);
+    require!(match_pool.total_allocated == 0, CustomError::PrizesAlreadyAllocated);
+    require!(!match_pool.distribution_started(), CustomError::DistributionInProgress);
+    require!(PrizeTier::is_valid_table(&prize_table), CustomError::InvalidPrizeTable);
+    require!(dust_sink != DustSink::Rollover(match_pool.key()), CustomError::InvalidDustAccount);
+
+    match_pool.prize_table = prize_table;
+    match_pool.dust_sink = dust_sink;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPrizeTable<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.is_operator(&admin.key()) @ CustomError::Unauthorized,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
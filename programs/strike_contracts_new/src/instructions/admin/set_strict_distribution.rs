@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, CustomError, MatchPool, MatchStatus};
+
+// Strict mode is on for every new pool. It only decides what `distribute_prizes`
+// does when a winner's token account is missing: fail the page, or, with strict
+// mode off, record the prize for the winner to pull with `claim_prize`.
+// Duplicate or unknown winners fail the page either way.
+pub fn process_set_strict_distribution(ctx: Context<SetStrictDistribution>, strict: bool) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+
+    match_pool.check_status(&[MatchStatus::Open, MatchStatus::Locked, MatchStatus::Scoring])?;
+    require!(!match_pool.distribution_started(), CustomError::DistributionInProgress);
+
+    match_pool.strict_distribution = strict;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetStrictDistribution<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.is_operator(&admin.key()) @ CustomError::Unauthorized,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
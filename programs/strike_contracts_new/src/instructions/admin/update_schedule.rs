@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, CustomError, MatchPool, MatchStatus, ScheduleUpdatedEvent};
+
+// Moves the contest schedule while registration is still open. The deadline can
+// only be pushed back, so entrants never lose time they were promised.
+pub fn process_update_schedule(
+    ctx: Context<UpdateSchedule>,
+    registration_end_time: i64,
+    match_start_time: i64,
+) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+
+    match_pool.check_status(&[MatchStatus::Open])?;
+
+    require!(
+        registration_end_time >= match_pool.registration_end_time,
+        CustomError::InvalidRegistrationEndTime
+    );
+    require!(match_start_time >= registration_end_time, CustomError::InvalidMatchStartTime);
+
+    match_pool.registration_end_time = registration_end_time;
+    match_pool.match_start_time = match_start_time;
+
+    emit!(ScheduleUpdatedEvent {
+        match_id: match_pool.match_id.clone(),
+        registration_end_time,
+        match_start_time,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateSchedule<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.is_operator(&admin.key()) @ CustomError::Unauthorized,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
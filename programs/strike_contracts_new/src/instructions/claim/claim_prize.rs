@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::utils::{transfer_from_pool, unwrap_if_native};
+use crate::{Config, CustomError, MatchPool, MatchStatus, PrizeClaimedEvent, TeamEntry};
+
+pub fn process_claim_prize(ctx: Context<ClaimPrize>, team_index: u16) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+    let team_entry = &mut ctx.accounts.team_entry;
+    let clock = Clock::get()?;
+
+    match_pool.check_status(&[MatchStatus::Scoring])?;
+    require!(match_pool.payouts_unlocked(clock.unix_timestamp), CustomError::PayoutsLocked);
+    require!(team_entry.prize_amount > 0, CustomError::NoPrizeToClaim);
+    require!(!team_entry.is_claimed, CustomError::PrizeAlreadyClaimed);
+    require!(clock.unix_timestamp < match_pool.claim_expiry, CustomError::ClaimExpired);
+
+    let amount = team_entry.prize_amount;
+
+    transfer_from_pool(
+        match_pool,
+        &ctx.accounts.pool_token_account,
+        ctx.accounts.winner_token_account.to_account_info(),
+        &ctx.accounts.token_mint,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    unwrap_if_native(
+        match_pool,
+        &ctx.accounts.winner_token_account,
+        ctx.accounts.winner.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+
+    team_entry.is_claimed = true;
+    match_pool.total_claimed = match_pool.total_claimed.checked_add(amount)
+        .ok_or(error!(CustomError::ArithmeticOverflow))?;
+
+    emit!(PrizeClaimedEvent {
+        user: team_entry.user,
+        match_id: match_pool.match_id.clone(),
+        team_index: Some(team_index),
+        amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(team_index: u16)]
+pub struct ClaimPrize<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.allows_exits(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_token", match_pool.match_id.as_bytes()],
+        bump = match_pool.token_bump,
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = match_pool.mint @ CustomError::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"team_entry", match_pool.match_id.as_bytes(), winner.key().as_ref(), &team_index.to_le_bytes()],
+        bump = team_entry.bump,
+    )]
+    pub team_entry: Account<'info, TeamEntry>,
+
+    #[account(
+        mut,
+        constraint = winner_token_account.mint == match_pool.mint @ CustomError::InvalidMint,
+        constraint = winner_token_account.owner == winner.key() @ CustomError::InvalidTokenOwner,
+    )]
+    pub winner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Receives the lamports when a SOL prize is unwrapped
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::merkle::{leaf_hash, verify_proof};
+use crate::utils::{transfer_from_pool, unwrap_if_native};
+use crate::{ClaimReceipt, Config, CustomError, MatchPool, MatchStatus, PrizeClaimedEvent};
+
+pub fn process_claim_with_proof(
+    ctx: Context<ClaimWithProof>,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+    let winner_key = ctx.accounts.winner.key();
+    let clock = Clock::get()?;
+
+    match_pool.check_status(&[MatchStatus::Scoring])?;
+    require!(match_pool.payouts_unlocked(clock.unix_timestamp), CustomError::PayoutsLocked);
+    require!(match_pool.merkle_root != [0u8; 32], CustomError::InvalidMerkleRoot);
+    require!(clock.unix_timestamp < match_pool.claim_expiry, CustomError::ClaimExpired);
+    require!(
+        verify_proof(&proof, &match_pool.merkle_root, leaf_hash(&winner_key, amount)),
+        CustomError::InvalidMerkleProof
+    );
+
+    let total_claimed = match_pool.total_claimed.checked_add(amount)
+        .ok_or(error!(CustomError::InsufficientPoolFunds))?;
+    require!(total_claimed <= match_pool.total_allocated, CustomError::InsufficientPoolFunds);
+
+    transfer_from_pool(
+        match_pool,
+        &ctx.accounts.pool_token_account,
+        ctx.accounts.winner_token_account.to_account_info(),
+        &ctx.accounts.token_mint,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    unwrap_if_native(
+        match_pool,
+        &ctx.accounts.winner_token_account,
+        ctx.accounts.winner.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+
+    // The receipt's `init` is what stops a second claim for the same leaf
+    let claim_receipt = &mut ctx.accounts.claim_receipt;
+    claim_receipt.user = winner_key;
+    claim_receipt.match_pool = match_pool.key();
+    claim_receipt.amount = amount;
+    claim_receipt.bump = ctx.bumps.claim_receipt;
+
+    match_pool.total_claimed = total_claimed;
+
+    emit!(PrizeClaimedEvent {
+        user: winner_key,
+        match_id: match_pool.match_id.clone(),
+        team_index: None,
+        amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimWithProof<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.allows_exits(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_token", match_pool.match_id.as_bytes()],
+        bump = match_pool.token_bump,
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = match_pool.mint @ CustomError::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = winner,
+        space = 8 + ClaimReceipt::INIT_SPACE,
+        seeds = [b"claim_receipt", match_pool.match_id.as_bytes(), winner.key().as_ref()],
+        bump,
+    )]
+    pub claim_receipt: Account<'info, ClaimReceipt>,
+
+    #[account(
+        mut,
+        constraint = winner_token_account.mint == match_pool.mint @ CustomError::InvalidMint,
+        constraint = winner_token_account.owner == winner.key() @ CustomError::InvalidTokenOwner,
+    )]
+    pub winner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Pays for the receipt and receives the lamports when a SOL prize is unwrapped
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
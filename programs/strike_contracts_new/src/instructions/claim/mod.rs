@@ -0,0 +1,4 @@
+pub use claim_prize::*;
+mod claim_prize;
+pub use claim_with_proof::*;
+mod claim_with_proof;
\ No newline at end of file
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::CustomError, program::StrikeContractsNew, state::Config};
+
+// Creates the program-wide config singleton. Only the program's upgrade
+// authority can run it, so nobody can front-run the deployment and take over.
+pub fn process_initialize_config(ctx: Context<InitializeConfig>, treasury: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.super_admin = ctx.accounts.super_admin.key();
+    config.pending_super_admin = Pubkey::default();
+    config.operators = Vec::new();
+    config.treasury = treasury;
+    config.oracle = Pubkey::default();
+    config.challenge_period = Config::DEFAULT_CHALLENGE_PERIOD;
+    config.guardian = ctx.accounts.super_admin.key();
+    config.paused = false;
+    config.exits_when_paused = true;
+    config.bump = ctx.bumps.config;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = super_admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ CustomError::Unauthorized,
+    )]
+    pub program: Program<'info, StrikeContractsNew>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(super_admin.key()) @ CustomError::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub super_admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, CustomError, OperatorAddedEvent, OperatorRemovedEvent};
+
+pub fn process_add_operator(ctx: Context<ManageOperators>, operator: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    require!(!config.operators.contains(&operator), CustomError::OperatorAlreadyExists);
+    require!(config.operators.len() < Config::MAX_OPERATORS, CustomError::TooManyOperators);
+
+    config.operators.push(operator);
+
+    emit!(OperatorAddedEvent { operator });
+
+    Ok(())
+}
+
+pub fn process_remove_operator(ctx: Context<ManageOperators>, operator: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    let index = config.operators.iter().position(|o| *o == operator)
+        .ok_or(error!(CustomError::OperatorNotFound))?;
+    config.operators.swap_remove(index);
+
+    emit!(OperatorRemovedEvent { operator });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ManageOperators<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.super_admin == super_admin.key() @ CustomError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub super_admin: Signer<'info>,
+}
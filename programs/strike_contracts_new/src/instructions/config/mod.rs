@@ -0,0 +1,20 @@
+pub use initialize_config::*;
+mod initialize_config;
+pub use manage_operators::*;
+mod manage_operators;
+pub use set_treasury::*;
+mod set_treasury;
+pub use transfer_super_admin::*;
+mod transfer_super_admin;
+pub use set_oracle::*;
+mod set_oracle;
+pub use set_challenge_period::*;
+mod set_challenge_period;
+pub use set_allowed_mint::*;
+mod set_allowed_mint;
+pub use remove_allowed_mint::*;
+mod remove_allowed_mint;
+pub use set_guardian::*;
+mod set_guardian;
+pub use set_paused::*;
+mod set_paused;
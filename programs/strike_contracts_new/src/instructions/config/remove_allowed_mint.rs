@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{AllowedMint, AllowedMintRemovedEvent, Config, CustomError};
+
+// Stops new contests in a mint. Pools already created in it are unaffected.
+pub fn process_remove_allowed_mint(ctx: Context<RemoveAllowedMint>) -> Result<()> {
+    emit!(AllowedMintRemovedEvent {
+        mint: ctx.accounts.allowed_mint.mint,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveAllowedMint<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.super_admin == super_admin.key() @ CustomError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = super_admin,
+        seeds = [b"allowed_mint", allowed_mint.mint.as_ref()],
+        bump = allowed_mint.bump,
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+
+    #[account(mut)]
+    pub super_admin: Signer<'info>,
+}
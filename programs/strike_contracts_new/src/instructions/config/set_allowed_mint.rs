@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{AllowedMint, AllowedMintUpdatedEvent, Config, CustomError};
+
+// Allowlists a mint for new contests, or updates the entry fees accepted for it
+pub fn process_set_allowed_mint(
+    ctx: Context<SetAllowedMint>,
+    min_entry_fee: u64,
+    max_entry_fee: u64,
+) -> Result<()> {
+    require!(min_entry_fee > 0 && min_entry_fee <= max_entry_fee, CustomError::InvalidEntryFeeRange);
+
+    let allowed_mint = &mut ctx.accounts.allowed_mint;
+    allowed_mint.mint = ctx.accounts.token_mint.key();
+    allowed_mint.min_entry_fee = min_entry_fee;
+    allowed_mint.max_entry_fee = max_entry_fee;
+    allowed_mint.bump = ctx.bumps.allowed_mint;
+
+    emit!(AllowedMintUpdatedEvent {
+        mint: allowed_mint.mint,
+        min_entry_fee,
+        max_entry_fee,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetAllowedMint<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.super_admin == super_admin.key() @ CustomError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = super_admin,
+        space = 8 + AllowedMint::INIT_SPACE,
+        seeds = [b"allowed_mint", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub super_admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::{ChallengePeriodUpdatedEvent, Config, CustomError};
+
+// How long, in seconds, participants have to dispute posted results before
+// payouts unlock. Applies to results posted afterwards. It can never be zero,
+// or no dispute could ever be raised.
+pub fn process_set_challenge_period(ctx: Context<SetChallengePeriod>, challenge_period: i64) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    require!(challenge_period > 0, CustomError::InvalidChallengePeriod);
+
+    config.challenge_period = challenge_period;
+
+    emit!(ChallengePeriodUpdatedEvent { challenge_period });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetChallengePeriod<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.super_admin == super_admin.key() @ CustomError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub super_admin: Signer<'info>,
+}
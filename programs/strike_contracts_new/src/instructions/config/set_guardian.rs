@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, CustomError, GuardianUpdatedEvent};
+
+// The guardian can only pause and unpause, so it can be a hot key kept by on-call
+pub fn process_set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.guardian = guardian;
+
+    emit!(GuardianUpdatedEvent { guardian });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.super_admin == super_admin.key() @ CustomError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub super_admin: Signer<'info>,
+}
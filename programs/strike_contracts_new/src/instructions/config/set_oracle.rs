@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, CustomError, OracleUpdatedEvent};
+
+// The oracle is the only signer allowed to post player points for any match
+pub fn process_set_oracle(ctx: Context<SetOracle>, oracle: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.oracle = oracle;
+
+    emit!(OracleUpdatedEvent { oracle });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetOracle<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.super_admin == super_admin.key() @ CustomError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub super_admin: Signer<'info>,
+}
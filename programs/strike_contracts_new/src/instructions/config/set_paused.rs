@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, CustomError, ProgramPausedEvent};
+
+// Emergency switch for every pool at once. While paused, refunds and claims
+// stay available only if `exits_when_paused` is set.
+pub fn process_set_paused(ctx: Context<SetPaused>, paused: bool, exits_when_paused: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.paused = paused;
+    config.exits_when_paused = exits_when_paused;
+
+    emit!(ProgramPausedEvent {
+        paused,
+        exits_when_paused,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.can_pause(&guardian.key()) @ CustomError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub guardian: Signer<'info>,
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, CustomError, TreasuryUpdatedEvent};
+
+// Only affects pools created afterwards, existing pools keep their treasury account
+pub fn process_set_treasury(ctx: Context<SetTreasury>, treasury: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.treasury = treasury;
+
+    emit!(TreasuryUpdatedEvent { treasury });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.super_admin == super_admin.key() @ CustomError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub super_admin: Signer<'info>,
+}
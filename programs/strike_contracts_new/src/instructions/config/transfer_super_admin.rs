@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, CustomError, SuperAdminTransferredEvent};
+
+// First step of the handover, the new key has to accept before it takes effect
+pub fn process_transfer_super_admin(ctx: Context<TransferSuperAdmin>, new_super_admin: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.pending_super_admin = new_super_admin;
+
+    Ok(())
+}
+
+pub fn process_accept_super_admin(ctx: Context<AcceptSuperAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let previous_super_admin = config.super_admin;
+
+    config.super_admin = config.pending_super_admin;
+    config.pending_super_admin = Pubkey::default();
+
+    emit!(SuperAdminTransferredEvent {
+        previous_super_admin,
+        new_super_admin: config.super_admin,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct TransferSuperAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.super_admin == super_admin.key() @ CustomError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub super_admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptSuperAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.pending_super_admin == new_super_admin.key() @ CustomError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub new_super_admin: Signer<'info>,
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, CustomError, MatchPool, MatchStatus, UserDeposit};
+
+// Gives the user back the rent of their deposit record once the pool is
+// settled. Deposits in a cancelled pool are closed by `refund_deposit`.
+pub fn process_close_user_deposit(ctx: Context<CloseUserDeposit>) -> Result<()> {
+    ctx.accounts.match_pool.check_status(&[MatchStatus::Settled, MatchStatus::Closed])?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseUserDeposit<'info> {
+    #[account(
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.allows_exits(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"user_deposit", match_pool.match_id.as_bytes(), user.key().as_ref()],
+        bump = user_deposit.bump,
+        close = user,
+    )]
+    pub user_deposit: Account<'info, UserDeposit>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{sync_native, SyncNative, TokenAccount, TokenInterface};
+
+use crate::utils::{entry_cost, record_entries};
+use crate::{Config, ContestEnteredEvent, CustomError, MatchPool, MatchStatus, UserDeposit};
+
+// `enter_contest` for SOL contests, paid in lamports from the user's wallet.
+// They are wrapped straight into the pool's wSOL account, so the rest of the
+// contest treats them like any other SPL deposit.
+pub fn process_deposit_sol(ctx: Context<DepositSol>, entries: u32) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+
+    match_pool.check_status(&[MatchStatus::Open])?;
+    require!(match_pool.is_native(), CustomError::NotNativeMint);
+
+    let amount = entry_cost(match_pool, &ctx.accounts.user_deposit, entries)?;
+
+    // Stray lamports already in the pool account are synced first so they are never credited
+    sync_native(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        SyncNative { account: ctx.accounts.pool_token_account.to_account_info() },
+    ))?;
+    ctx.accounts.pool_token_account.reload()?;
+    let balance_before = ctx.accounts.pool_token_account.amount;
+
+    let transfer_cpi_accounts = Transfer {
+        from: ctx.accounts.user.to_account_info(),
+        to: ctx.accounts.pool_token_account.to_account_info(),
+    };
+    transfer(
+        CpiContext::new(ctx.accounts.system_program.to_account_info(), transfer_cpi_accounts),
+        amount,
+    )?;
+
+    sync_native(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        SyncNative { account: ctx.accounts.pool_token_account.to_account_info() },
+    ))?;
+
+    ctx.accounts.pool_token_account.reload()?;
+    let received = ctx.accounts.pool_token_account.amount.checked_sub(balance_before)
+        .ok_or(error!(CustomError::TokenTransferError))?;
+    require!(received == amount, CustomError::TokenTransferError);
+
+    let user_key = ctx.accounts.user.key();
+    record_entries(
+        match_pool,
+        &mut ctx.accounts.user_deposit,
+        user_key,
+        ctx.bumps.user_deposit,
+        entries,
+        received,
+    )?;
+
+    emit!(ContestEnteredEvent {
+        user: user_key,
+        match_id: match_pool.match_id.clone(),
+        entries,
+        amount: received,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DepositSol<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_token", match_pool.match_id.as_bytes()],
+        bump = match_pool.token_bump,
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserDeposit::INIT_SPACE,
+        seeds = [b"user_deposit", match_pool.match_id.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub user_deposit: Account<'info, UserDeposit>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::utils::{entry_cost, record_entries};
+use crate::{Config, ContestEnteredEvent, CustomError, MatchPool, MatchStatus, UserDeposit};
+
+// Buys `entries` contest entries at the pool's fixed entry fee
+pub fn process_enter_contest(ctx: Context<EnterContest>, entries: u32) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+
+    match_pool.check_status(&[MatchStatus::Open])?;
+
+    let amount = entry_cost(match_pool, &ctx.accounts.user_deposit, entries)?;
+
+    // Transfer tokens from user to pool
+    let transfer_cpi_accounts = TransferChecked {
+        from: ctx.accounts.user_token_account.to_account_info(),
+        mint: ctx.accounts.token_mint.to_account_info(),
+        to: ctx.accounts.pool_token_account.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, transfer_cpi_accounts);
+
+    let balance_before = ctx.accounts.pool_token_account.amount;
+    transfer_checked(cpi_ctx, amount, ctx.accounts.token_mint.decimals)?;
+
+    // Only credit what actually arrived, Token-2022 transfer fees are withheld from it
+    ctx.accounts.pool_token_account.reload()?;
+    let received = ctx.accounts.pool_token_account.amount.checked_sub(balance_before)
+        .ok_or(error!(CustomError::TokenTransferError))?;
+
+    let user_key = ctx.accounts.user.key();
+    record_entries(
+        match_pool,
+        &mut ctx.accounts.user_deposit,
+        user_key,
+        ctx.bumps.user_deposit,
+        entries,
+        received,
+    )?;
+
+    emit!(ContestEnteredEvent {
+        user: user_key,
+        match_id: match_pool.match_id.clone(),
+        entries,
+        amount: received,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct EnterContest<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_token", match_pool.match_id.as_bytes()],
+        bump = match_pool.token_bump,
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = match_pool.mint @ CustomError::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserDeposit::INIT_SPACE,
+        seeds = [b"user_deposit", match_pool.match_id.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub user_deposit: Account<'info, UserDeposit>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == match_pool.mint @ CustomError::InvalidMint,
+        constraint = user_token_account.owner == user.key() @ CustomError::InvalidTokenOwner,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
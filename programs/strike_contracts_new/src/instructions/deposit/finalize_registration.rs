@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, CustomError, MatchPool, MatchStatus, RegistrationFinalizedEvent};
+
+// Permissionless crank for once the registration deadline has passed. A contest
+// short of its minimum headcount is cancelled so every entry can be refunded
+// with `refund_deposit`, otherwise it is locked for play. It only acts while the
+// lock has not been written yet, and the headcount cannot change after the
+// deadline, so every crank reaches the same outcome.
+pub fn process_finalize_registration(ctx: Context<FinalizeRegistration>) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+
+    match_pool.check_status(&[MatchStatus::Locked])?;
+    require!(match_pool.status == MatchStatus::Open, CustomError::InvalidMatchStatus);
+
+    let cancelled = !match_pool.has_enough_participants();
+    if cancelled {
+        match_pool.transition_to(MatchStatus::Cancelled)?;
+    } else {
+        match_pool.sync_status()?;
+    }
+
+    emit!(RegistrationFinalizedEvent {
+        match_id: match_pool.match_id.clone(),
+        participant_count: match_pool.participant_count,
+        cancelled,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinalizeRegistration<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub cranker: Signer<'info>,
+}
@@ -1,2 +1,10 @@
-pub use deposit::*;
-mod deposit;
\ No newline at end of file
+pub use enter_contest::*;
+mod enter_contest;
+pub use withdraw_deposit::*;
+mod withdraw_deposit;
+pub use finalize_registration::*;
+mod finalize_registration;
+pub use deposit_sol::*;
+mod deposit_sol;
+pub use close_user_deposit::*;
+mod close_user_deposit;
\ No newline at end of file
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::utils::transfer_from_pool;
+use crate::{Config, CustomError, MatchPool, MatchStatus, UserDeposit, WithdrawEvent};
+
+// Gives back `entries` unused entries while registration is open. The refund is
+// the entry fee per entry, capped at what the user actually paid in, and leaving
+// the contest entirely closes the deposit record.
+pub fn process_withdraw_deposit(ctx: Context<WithdrawDeposit>, entries: u32) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+    let user_deposit = &mut ctx.accounts.user_deposit;
+
+    match_pool.check_status(&[MatchStatus::Open])?;
+    require!(entries > 0 && entries <= user_deposit.entries, CustomError::InvalidEntryCount);
+    require!(entries <= user_deposit.unused_entries(), CustomError::EntriesInUse);
+
+    let remaining_entries = user_deposit.entries - entries;
+    let amount = user_deposit.stake_for(entries, match_pool.entry_fee);
+
+    if amount > 0 {
+        transfer_from_pool(
+            match_pool,
+            &ctx.accounts.pool_token_account,
+            ctx.accounts.user_token_account.to_account_info(),
+            &ctx.accounts.token_mint,
+            &ctx.accounts.token_program,
+            amount,
+        )?;
+    }
+
+    user_deposit.amount -= amount;
+    user_deposit.entries = remaining_entries;
+    match_pool.total_deposited = match_pool.total_deposited.checked_sub(amount)
+        .ok_or(error!(CustomError::InsufficientPoolFunds))?;
+
+    emit!(WithdrawEvent {
+        user: user_deposit.user,
+        match_id: match_pool.match_id.clone(),
+        entries,
+        amount,
+    });
+
+    if remaining_entries == 0 {
+        match_pool.participant_count -= 1;
+        user_deposit.close(ctx.accounts.user.to_account_info())?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawDeposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.allows_exits(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_token", match_pool.match_id.as_bytes()],
+        bump = match_pool.token_bump,
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = match_pool.mint @ CustomError::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"user_deposit", match_pool.match_id.as_bytes(), user.key().as_ref()],
+        bump = user_deposit.bump,
+    )]
+    pub user_deposit: Account<'info, UserDeposit>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == match_pool.mint @ CustomError::InvalidMint,
+        constraint = user_token_account.owner == user.key() @ CustomError::InvalidTokenOwner,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
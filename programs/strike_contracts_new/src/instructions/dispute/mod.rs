@@ -0,0 +1,6 @@
+pub use raise_dispute::*;
+mod raise_dispute;
+pub use resolve_dispute::*;
+mod resolve_dispute;
+pub use void_results::*;
+mod void_results;
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::{Config, CustomError, Dispute, DisputeRaisedEvent, MatchPool, MatchStatus, UserDeposit};
+
+// Lets a participant challenge posted results before the window closes. The
+// bond is one entry fee, held in the pool until the super-admin resolves the
+// dispute. `reason` is the hash of an off-chain explanation.
+pub fn process_raise_dispute(ctx: Context<RaiseDispute>, reason: [u8; 32]) -> Result<()> {
+    let clock = Clock::get()?;
+
+    ctx.accounts.match_pool.check_status(&[MatchStatus::Scoring, MatchStatus::Disputed])?;
+    require!(ctx.accounts.match_pool.results_posted(), CustomError::ResultsNotPosted);
+    require!(
+        clock.unix_timestamp < ctx.accounts.match_pool.challenge_end_time,
+        CustomError::ChallengeWindowClosed
+    );
+
+    let transfer_cpi_accounts = TransferChecked {
+        from: ctx.accounts.user_token_account.to_account_info(),
+        mint: ctx.accounts.token_mint.to_account_info(),
+        to: ctx.accounts.pool_token_account.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, transfer_cpi_accounts);
+
+    let balance_before = ctx.accounts.pool_token_account.amount;
+    transfer_checked(cpi_ctx, ctx.accounts.match_pool.entry_fee, ctx.accounts.token_mint.decimals)?;
+
+    // Only hold what actually arrived, so returning the bond can never dip into prizes
+    ctx.accounts.pool_token_account.reload()?;
+    let bond = ctx.accounts.pool_token_account.amount.checked_sub(balance_before)
+        .ok_or(error!(CustomError::TokenTransferError))?;
+
+    let match_pool = &mut ctx.accounts.match_pool;
+    let dispute = &mut ctx.accounts.dispute;
+
+    dispute.disputer = ctx.accounts.user.key();
+    dispute.match_pool = match_pool.key();
+    dispute.bond = bond;
+    dispute.reason = reason;
+    dispute.bump = ctx.bumps.dispute;
+
+    if match_pool.status == MatchStatus::Scoring {
+        match_pool.transition_to(MatchStatus::Disputed)?;
+    }
+    match_pool.open_disputes += 1;
+
+    emit!(DisputeRaisedEvent {
+        user: dispute.disputer,
+        match_id: match_pool.match_id.clone(),
+        bond,
+        reason,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RaiseDispute<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_token", match_pool.match_id.as_bytes()],
+        bump = match_pool.token_bump,
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = match_pool.mint @ CustomError::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Only participants can dispute
+    #[account(
+        seeds = [b"user_deposit", match_pool.match_id.as_bytes(), user.key().as_ref()],
+        bump = user_deposit.bump,
+    )]
+    pub user_deposit: Account<'info, UserDeposit>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Dispute::INIT_SPACE,
+        seeds = [b"dispute", match_pool.match_id.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == match_pool.mint @ CustomError::InvalidMint,
+        constraint = user_token_account.owner == user.key() @ CustomError::InvalidTokenOwner,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
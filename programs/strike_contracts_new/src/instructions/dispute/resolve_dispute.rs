@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::utils::transfer_from_pool;
+use crate::{Config, CustomError, Dispute, DisputeResolvedEvent, MatchPool, MatchStatus};
+
+// Settles a dispute. An upheld dispute returns the bond and voids the posted
+// results, so the operator has to correct and post them again. A rejected one
+// forfeits the bond to the treasury. Settlement resumes once no dispute is open,
+// and payouts still wait for the challenge window to close.
+pub fn process_resolve_dispute(ctx: Context<ResolveDispute>, upheld: bool) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+    let dispute = &ctx.accounts.dispute;
+    let bond_token_account = &ctx.accounts.bond_token_account;
+
+    match_pool.check_status(&[MatchStatus::Disputed])?;
+
+    if upheld {
+        require!(bond_token_account.owner == dispute.disputer, CustomError::InvalidTokenOwner);
+    } else {
+        require!(bond_token_account.key() == match_pool.treasury, CustomError::InvalidTreasury);
+    }
+
+    if dispute.bond > 0 {
+        transfer_from_pool(
+            match_pool,
+            &ctx.accounts.pool_token_account,
+            bond_token_account.to_account_info(),
+            &ctx.accounts.token_mint,
+            &ctx.accounts.token_program,
+            dispute.bond,
+        )?;
+    }
+
+    if upheld {
+        match_pool.void_results();
+    }
+
+    match_pool.open_disputes -= 1;
+    if match_pool.open_disputes == 0 {
+        match_pool.transition_to(MatchStatus::Scoring)?;
+    }
+
+    emit!(DisputeResolvedEvent {
+        user: dispute.disputer,
+        match_id: match_pool.match_id.clone(),
+        upheld,
+        bond: dispute.bond,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.super_admin == super_admin.key() @ CustomError::Unauthorized,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", match_pool.match_id.as_bytes(), disputer.key().as_ref()],
+        bump = dispute.bump,
+        close = disputer,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_token", match_pool.match_id.as_bytes()],
+        bump = match_pool.token_bump,
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = match_pool.mint @ CustomError::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// The disputer's token account if upheld, the pool's treasury otherwise
+    #[account(
+        mut,
+        constraint = bond_token_account.mint == match_pool.mint @ CustomError::InvalidMint,
+    )]
+    pub bond_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: The disputer, only receives the bond and the dispute's rent
+    #[account(mut, address = dispute.disputer @ CustomError::Unauthorized)]
+    pub disputer: AccountInfo<'info>,
+
+    pub super_admin: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
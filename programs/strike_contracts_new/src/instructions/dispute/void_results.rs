@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, CustomError, MatchPool, MatchStatus, ResultsVoidedEvent};
+
+// Lets the super-admin throw away posted results that cannot be settled, such
+// as a winner count the results tree does not cover, so corrected ones can be
+// posted. Only while nothing has been claimed and the distribution is not done;
+// open disputes are resolved first.
+pub fn process_void_results(ctx: Context<VoidResults>) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+
+    match_pool.check_status(&[MatchStatus::Scoring])?;
+    require!(match_pool.results_posted(), CustomError::ResultsNotPosted);
+    require!(match_pool.can_void_results(), CustomError::PayoutsStarted);
+
+    match_pool.void_results();
+
+    emit!(ResultsVoidedEvent {
+        match_id: match_pool.match_id.clone(),
+        distribution_cursor: match_pool.distribution_cursor,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VoidResults<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.super_admin == super_admin.key() @ CustomError::Unauthorized,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub super_admin: Signer<'info>,
+}
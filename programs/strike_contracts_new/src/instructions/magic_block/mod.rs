@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use ephemeral_rollups_sdk::anchor::{commit, delegate};
-use crate::state::MatchPool;
-use anchor_spl::token::TokenAccount;
+use crate::state::{Config, MatchPool};
+use anchor_spl::token_interface::TokenAccount;
 
 /// Account structure for delegating a match pool to MagicBlock Ephemeral Rollups
 #[delegate]
@@ -15,10 +15,17 @@ pub struct DelegateMatchPool<'info> {
         mut,
         seeds = [b"match_pool", match_pool.match_id.as_bytes()],
         bump = match_pool.bump,
-        constraint = match_pool.admin == admin.key() @ crate::error::CustomError::Unauthorized,
         del
     )]
     pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.is_operator(&admin.key()) @ crate::error::CustomError::Unauthorized,
+        constraint = !config.is_paused(&match_pool) @ crate::error::CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
 }
 
 /// Account structure for delegating a pool token account to MagicBlock Ephemeral Rollups
@@ -32,9 +39,16 @@ pub struct DelegatePoolTokenAccount<'info> {
     #[account(
         seeds = [b"match_pool", match_pool.match_id.as_bytes()],
         bump = match_pool.bump,
-        constraint = match_pool.admin == admin.key() @ crate::error::CustomError::Unauthorized,
     )]
     pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.is_operator(&admin.key()) @ crate::error::CustomError::Unauthorized,
+        constraint = !config.is_paused(&match_pool) @ crate::error::CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
     
     /// The pool token account to delegate
     #[account(
@@ -43,7 +57,7 @@ pub struct DelegatePoolTokenAccount<'info> {
         bump = match_pool.token_bump,
         del
     )]
-    pub pool_token_account: Account<'info, TokenAccount>,
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
 }
 
 /// Account structure for delegating a user deposit to MagicBlock Ephemeral Rollups
@@ -59,8 +73,15 @@ pub struct DelegateUserDeposit<'info> {
         bump = match_pool.bump,
     )]
     pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(&match_pool) @ crate::error::CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
     
-    /// CHECK: The user deposit to delegate - will be handled by the delegation program
+    /// CHECK: The user deposit created by `enter_contest` - will be handled by the delegation program
     #[account(
         mut, 
         seeds = [b"user_deposit", match_pool.match_id.as_bytes(), user.key().as_ref()],
@@ -83,14 +104,22 @@ pub struct DelegateUserTokenAccount<'info> {
         bump = match_pool.bump,
     )]
     pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(&match_pool) @ crate::error::CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
     
     /// The user token account to delegate
     #[account(
         mut,
-        constraint = user_token_account.owner == user.key() @ crate::error::CustomError::Unauthorized,
+        constraint = user_token_account.mint == match_pool.mint @ crate::error::CustomError::InvalidMint,
+        constraint = user_token_account.owner == user.key() @ crate::error::CustomError::InvalidTokenOwner,
         del
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 }
 
 /// Account structure for committing or undelegating a match pool from MagicBlock Ephemeral Rollups
@@ -105,7 +134,13 @@ pub struct CommitMatchPool<'info> {
         mut,
         seeds = [b"match_pool", match_pool.match_id.as_bytes()],
         bump = match_pool.bump,
-        constraint = match_pool.admin == admin.key() @ crate::error::CustomError::Unauthorized,
     )]
     pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.is_operator(&admin.key()) @ crate::error::CustomError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
 }
\ No newline at end of file
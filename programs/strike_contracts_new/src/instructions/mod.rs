@@ -1,7 +1,21 @@
 pub use admin::*;
 mod admin;
+pub use config::*;
+mod config;
 pub use deposit::*;
 mod deposit;
+pub use team::*;
+mod team;
+pub use scoring::*;
+mod scoring;
+pub use dispute::*;
+mod dispute;
+pub use sponsor::*;
+mod sponsor;
+pub use refund::*;
+mod refund;
+pub use claim::*;
+mod claim;
 pub use magic_block::*;
 mod magic_block;
 pub use token_transfer::*;
@@ -0,0 +1,2 @@
+pub use refund_deposit::*;
+mod refund_deposit;
\ No newline at end of file
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::utils::transfer_from_pool;
+use crate::{Config, CustomError, MatchPool, MatchStatus, RefundEvent, UserDeposit};
+
+// Refunds a user's full deposit from a cancelled match. Anyone may crank this,
+// the tokens always go to the depositor and the record's rent back to them.
+pub fn process_refund_deposit(ctx: Context<RefundDeposit>) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+    let user_deposit = &mut ctx.accounts.user_deposit;
+
+    match_pool.check_status(&[MatchStatus::Cancelled])?;
+
+    let amount = user_deposit.amount;
+
+    if amount > 0 {
+        transfer_from_pool(
+            match_pool,
+            &ctx.accounts.pool_token_account,
+            ctx.accounts.user_token_account.to_account_info(),
+            &ctx.accounts.token_mint,
+            &ctx.accounts.token_program,
+            amount,
+        )?;
+    }
+
+    user_deposit.amount = 0;
+    match_pool.total_deposited = match_pool.total_deposited.checked_sub(amount)
+        .ok_or(error!(CustomError::InsufficientPoolFunds))?;
+
+    emit!(RefundEvent {
+        user: user_deposit.user,
+        match_id: match_pool.match_id.clone(),
+        amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RefundDeposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.allows_exits(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_token", match_pool.match_id.as_bytes()],
+        bump = match_pool.token_bump,
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = match_pool.mint @ CustomError::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"user_deposit", match_pool.match_id.as_bytes(), user.key().as_ref()],
+        bump = user_deposit.bump,
+        close = user,
+    )]
+    pub user_deposit: Account<'info, UserDeposit>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == match_pool.mint @ CustomError::InvalidMint,
+        constraint = user_token_account.owner == user.key() @ CustomError::InvalidTokenOwner,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: The depositor, only receives the refund and the record's rent
+    #[account(mut, address = user_deposit.user @ CustomError::Unauthorized)]
+    pub user: AccountInfo<'info>,
+
+    pub cranker: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
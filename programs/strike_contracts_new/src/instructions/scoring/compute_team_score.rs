@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, CustomError, MatchPool, MatchScores, MatchStatus, TeamEntry, TeamScoredEvent};
+
+// Permissionless: anyone can score a revealed team once the oracle has
+// finalized the player points.
+pub fn process_compute_team_score(
+    ctx: Context<ComputeTeamScore>,
+    _user: Pubkey,
+    team_index: u16,
+) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+    let team_entry = &mut ctx.accounts.team_entry;
+
+    match_pool.check_status(&[MatchStatus::Scoring])?;
+    require!(team_entry.is_revealed, CustomError::TeamNotRevealed);
+    require!(!team_entry.is_scored, CustomError::TeamAlreadyScored);
+
+    team_entry.score = ctx.accounts.match_scores.team_score(&team_entry.lineup);
+    team_entry.is_scored = true;
+    match_pool.scored_count += 1;
+
+    emit!(TeamScoredEvent {
+        user: team_entry.user,
+        match_id: match_pool.match_id.clone(),
+        team_index,
+        score: team_entry.score,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey, team_index: u16)]
+pub struct ComputeTeamScore<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [b"match_scores", match_pool.match_id.as_bytes()],
+        bump = match_scores.bump,
+        constraint = match_scores.is_final @ CustomError::ScoresNotFinal,
+    )]
+    pub match_scores: Account<'info, MatchScores>,
+
+    #[account(
+        mut,
+        seeds = [b"team_entry", match_pool.match_id.as_bytes(), user.as_ref(), &team_index.to_le_bytes()],
+        bump = team_entry.bump,
+    )]
+    pub team_entry: Account<'info, TeamEntry>,
+}
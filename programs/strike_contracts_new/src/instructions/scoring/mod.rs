@@ -0,0 +1,6 @@
+pub use submit_scores::*;
+mod submit_scores;
+pub use compute_team_score::*;
+mod compute_team_score;
+pub use rank_and_settle::*;
+mod rank_and_settle;
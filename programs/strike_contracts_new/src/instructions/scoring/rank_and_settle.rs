@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+
+use crate::utils::update_team_entry;
+use crate::{Config, CustomError, DustSink, LeaderboardSettledEvent, MatchPool, MatchScores, MatchStatus, TeamEntry};
+
+// Permissionless settlement from the final scores. Every revealed team must be
+// passed in `remaining_accounts`, best first, with equal scores ordered by team
+// entry address. Teams with equal scores tie, and each team's prize is written
+// onto its team entry, to be pulled with `claim_prize`. The whole leaderboard
+// goes in one transaction, so contests with more than
+// `MatchPool::MAX_SETTLEMENT_TEAMS` revealed teams cannot be settled this way.
+pub fn process_rank_and_settle<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, RankAndSettle<'info>>,
+) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+    let clock = Clock::get()?;
+
+    match_pool.check_status(&[MatchStatus::Scoring])?;
+    require!(!match_pool.results_posted(), CustomError::ResultsAlreadyPosted);
+    require!(match_pool.is_guarantee_funded(), CustomError::GuaranteeNotFunded);
+    require!(match_pool.total_allocated == 0, CustomError::PrizesAlreadyAllocated);
+    require!(!match_pool.distribution_started(), CustomError::DistributionInProgress);
+    require!(match_pool.merkle_root == [0u8; 32], CustomError::PrizesAlreadyAllocated);
+    require!(!match_pool.prize_table.is_empty(), CustomError::InvalidPrizeTable);
+    require!(
+        match_pool.revealed_count <= MatchPool::MAX_SETTLEMENT_TEAMS,
+        CustomError::LeaderboardTooLarge
+    );
+    require!(match_pool.scored_count == match_pool.revealed_count, CustomError::TeamNotScored);
+    require!(
+        ctx.remaining_accounts.len() == match_pool.revealed_count as usize,
+        CustomError::LeaderboardIncomplete
+    );
+
+    // A strict (score desc, address asc) order also rules out duplicates, so
+    // with the count check above the leaderboard holds every revealed team once
+    let mut ranks: Vec<u16> = Vec::with_capacity(ctx.remaining_accounts.len());
+    let mut previous: Option<(i64, Pubkey)> = None;
+    for (position, account) in ctx.remaining_accounts.iter().enumerate() {
+        require!(account.owner == ctx.program_id, CustomError::TeamEntryNotFound);
+        let team_entry = TeamEntry::try_deserialize(&mut &account.data.borrow()[..])?;
+
+        require!(team_entry.match_pool == match_pool.key(), CustomError::TeamEntryNotFound);
+        require!(team_entry.is_revealed, CustomError::TeamNotRevealed);
+        require!(team_entry.is_scored, CustomError::TeamNotScored);
+
+        let rank = match previous {
+            Some((score, key)) => {
+                require!(
+                    team_entry.score < score || (team_entry.score == score && account.key() > key),
+                    CustomError::InvalidLeaderboardOrder
+                );
+                if team_entry.score == score { ranks[position - 1] } else { position as u16 + 1 }
+            }
+            None => 1,
+        };
+        ranks.push(rank);
+        previous = Some((team_entry.score, account.key()));
+    }
+
+    let mut amounts = match_pool.prize_amounts(0, &ranks);
+
+    // Only the rounding remainder goes to first place, unfilled shares stay for the treasury
+    if match_pool.dust_sink == DustSink::FirstPlace {
+        let dust = match_pool.table_pot(ranks.len()).saturating_sub(amounts.iter().sum());
+        if let Some(first) = amounts.first_mut() {
+            *first += dust;
+        }
+    }
+    let mut total_allocated: u64 = 0;
+
+    for (account, &amount) in ctx.remaining_accounts.iter().zip(amounts.iter()) {
+        if amount == 0 {
+            continue;
+        }
+
+        update_team_entry(account, |team_entry| {
+            require!(team_entry.prize_amount == 0, CustomError::PrizeAlreadyAllocated);
+            team_entry.prize_amount = amount;
+            Ok(())
+        })?;
+
+        total_allocated = total_allocated.checked_add(amount)
+            .ok_or(error!(CustomError::ArithmeticOverflow))?;
+    }
+
+    require!(total_allocated > 0, CustomError::NoPrizeAllocations);
+
+    // Dust stays in the pool until `finalize_prize_claims` sweeps it to the dust sink
+    match_pool.total_allocated = total_allocated;
+    match_pool.leaderboard_settled = true;
+    match_pool.claim_expiry = clock.unix_timestamp + MatchPool::SETTLEMENT_CLAIM_WINDOW;
+
+    emit!(LeaderboardSettledEvent {
+        match_id: match_pool.match_id.clone(),
+        teams: match_pool.revealed_count,
+        total_allocated,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RankAndSettle<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [b"match_scores", match_pool.match_id.as_bytes()],
+        bump = match_scores.bump,
+        constraint = match_scores.is_final @ CustomError::ScoresNotFinal,
+    )]
+    pub match_scores: Account<'info, MatchScores>,
+}
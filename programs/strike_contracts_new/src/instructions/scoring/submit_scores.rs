@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, CustomError, MatchPool, MatchStatus, MatchScores, PlayerPoints, ScoresSubmittedEvent};
+
+// Posts or corrects player points for a match. Points can be streamed while the
+// match is live; once finalized after the match has ended they can no longer change.
+pub fn process_submit_scores(
+    ctx: Context<SubmitScores>,
+    points: Vec<PlayerPoints>,
+    finalize: bool,
+) -> Result<()> {
+    let match_pool = &ctx.accounts.match_pool;
+    let match_scores = &mut ctx.accounts.match_scores;
+
+    match_pool.check_status(&[MatchStatus::Open, MatchStatus::Locked, MatchStatus::Scoring])?;
+    require!(!match_scores.is_final, CustomError::ScoresFinalized);
+
+    // First submission for this match
+    if match_scores.match_pool == Pubkey::default() {
+        match_scores.match_pool = match_pool.key();
+        match_scores.bump = ctx.bumps.match_scores;
+    }
+
+    for player in points.into_iter() {
+        match match_scores.points.iter_mut().find(|p| p.player_id == player.player_id) {
+            Some(existing) => existing.points = player.points,
+            None => match_scores.points.push(player),
+        }
+    }
+
+    require!(match_scores.points.len() <= MatchScores::MAX_PLAYERS, CustomError::TooManyPlayers);
+
+    if finalize {
+        match_pool.check_status(&[MatchStatus::Scoring])?;
+        match_scores.is_final = true;
+    }
+
+    emit!(ScoresSubmittedEvent {
+        match_id: match_pool.match_id.clone(),
+        players: match_scores.points.len() as u32,
+        is_final: match_scores.is_final,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SubmitScores<'info> {
+    #[account(
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.oracle == oracle.key() @ CustomError::Unauthorized,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = oracle,
+        space = 8 + MatchScores::INIT_SPACE,
+        seeds = [b"match_scores", match_pool.match_id.as_bytes()],
+        bump,
+    )]
+    pub match_scores: Account<'info, MatchScores>,
+
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
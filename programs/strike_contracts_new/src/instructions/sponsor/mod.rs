@@ -0,0 +1,4 @@
+pub use sponsor_top_up::*;
+mod sponsor_top_up;
+pub use refund_sponsor::*;
+mod refund_sponsor;
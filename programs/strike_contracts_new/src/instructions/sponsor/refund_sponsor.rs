@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::utils::transfer_from_pool;
+use crate::{Config, CustomError, MatchPool, MatchStatus, SponsorRefundEvent};
+
+// Returns the sponsor funds winners didn't receive once the pool is settled,
+// or all of them if the match was cancelled. Anyone may crank this, the tokens
+// always go to the sponsor.
+pub fn process_refund_sponsor(ctx: Context<RefundSponsor>) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+
+    match_pool.check_status(&[MatchStatus::Settled, MatchStatus::Cancelled])?;
+
+    let amount = match_pool.unused_sponsorship();
+
+    if amount > 0 {
+        transfer_from_pool(
+            match_pool,
+            &ctx.accounts.pool_token_account,
+            ctx.accounts.sponsor_token_account.to_account_info(),
+            &ctx.accounts.token_mint,
+            &ctx.accounts.token_program,
+            amount,
+        )?;
+    }
+
+    match_pool.sponsored_amount -= amount;
+
+    emit!(SponsorRefundEvent {
+        sponsor: match_pool.sponsor,
+        match_id: match_pool.match_id.clone(),
+        amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RefundSponsor<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.allows_exits(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_token", match_pool.match_id.as_bytes()],
+        bump = match_pool.token_bump,
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = match_pool.mint @ CustomError::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = sponsor_token_account.mint == match_pool.mint @ CustomError::InvalidMint,
+        constraint = sponsor_token_account.owner == match_pool.sponsor @ CustomError::InvalidTokenOwner,
+    )]
+    pub sponsor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub cranker: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
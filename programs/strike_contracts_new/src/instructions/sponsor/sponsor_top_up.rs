@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::{Config, CustomError, MatchPool, MatchStatus, SponsorTopUpEvent};
+
+// Adds sponsor funds to back the pool's guaranteed amount. They are kept apart
+// from entry deposits and whatever the guarantee doesn't need goes back to the
+// sponsor through `refund_sponsor`. Only the sponsor an operator named with
+// `set_guaranteed_amount` can top up.
+pub fn process_sponsor_top_up(ctx: Context<SponsorTopUp>, amount: u64) -> Result<()> {
+    let sponsor_key = ctx.accounts.sponsor.key();
+
+    ctx.accounts.match_pool.check_status(&[MatchStatus::Open, MatchStatus::Locked, MatchStatus::Scoring])?;
+    require!(!ctx.accounts.match_pool.results_posted(), CustomError::ResultsAlreadyPosted);
+    require!(amount > 0, CustomError::InvalidPrizeAmount);
+    require!(
+        ctx.accounts.match_pool.sponsor != Pubkey::default() && ctx.accounts.match_pool.sponsor == sponsor_key,
+        CustomError::InvalidSponsor
+    );
+
+    let transfer_cpi_accounts = TransferChecked {
+        from: ctx.accounts.sponsor_token_account.to_account_info(),
+        mint: ctx.accounts.token_mint.to_account_info(),
+        to: ctx.accounts.pool_token_account.to_account_info(),
+        authority: ctx.accounts.sponsor.to_account_info(),
+    };
+
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, transfer_cpi_accounts);
+
+    let balance_before = ctx.accounts.pool_token_account.amount;
+    transfer_checked(cpi_ctx, amount, ctx.accounts.token_mint.decimals)?;
+
+    // Only credit what actually arrived, Token-2022 transfer fees are withheld from it
+    ctx.accounts.pool_token_account.reload()?;
+    let received = ctx.accounts.pool_token_account.amount.checked_sub(balance_before)
+        .ok_or(error!(CustomError::TokenTransferError))?;
+
+    let match_pool = &mut ctx.accounts.match_pool;
+    match_pool.sponsored_amount = match_pool.sponsored_amount.checked_add(received)
+        .ok_or(error!(CustomError::ArithmeticOverflow))?;
+
+    emit!(SponsorTopUpEvent {
+        sponsor: sponsor_key,
+        match_id: match_pool.match_id.clone(),
+        amount: received,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SponsorTopUp<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_token", match_pool.match_id.as_bytes()],
+        bump = match_pool.token_bump,
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = match_pool.mint @ CustomError::InvalidMint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = sponsor_token_account.mint == match_pool.mint @ CustomError::InvalidMint,
+        constraint = sponsor_token_account.owner == sponsor.key() @ CustomError::InvalidTokenOwner,
+    )]
+    pub sponsor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub sponsor: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
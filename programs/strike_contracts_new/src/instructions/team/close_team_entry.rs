@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, CustomError, MatchPool, MatchStatus, TeamEntry};
+
+// Gives the user back the rent of a team entry once the pool is settled or
+// cancelled. By then every prize has been paid, claimed or swept.
+pub fn process_close_team_entry(ctx: Context<CloseTeamEntry>, _team_index: u16) -> Result<()> {
+    ctx.accounts.match_pool.check_status(&[
+        MatchStatus::Settled,
+        MatchStatus::Cancelled,
+        MatchStatus::Closed,
+    ])?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(team_index: u16)]
+pub struct CloseTeamEntry<'info> {
+    #[account(
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.allows_exits(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"team_entry", match_pool.match_id.as_bytes(), user.key().as_ref(), &team_index.to_le_bytes()],
+        bump = team_entry.bump,
+        close = user,
+    )]
+    pub team_entry: Account<'info, TeamEntry>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, CustomError, MatchPool, MatchStatus, TeamCommittedEvent, TeamEntry, TeamLineup, UserDeposit};
+
+// Commits a team against one of the user's paid entries without revealing it.
+// `commitment` is `TeamLineup::commitment`, checked later by `reveal_team`.
+// Teams take the entries in order, so `team_index` must be the user's next unused one.
+pub fn process_commit_team(ctx: Context<CommitTeam>, team_index: u16, commitment: [u8; 32]) -> Result<()> {
+    let match_pool = &ctx.accounts.match_pool;
+    let user_deposit = &mut ctx.accounts.user_deposit;
+
+    match_pool.check_status(&[MatchStatus::Open])?;
+    require!(
+        team_index as u32 == user_deposit.team_count && user_deposit.team_count < user_deposit.entries,
+        CustomError::InvalidTeamIndex
+    );
+
+    let team_entry = &mut ctx.accounts.team_entry;
+    team_entry.user = ctx.accounts.user.key();
+    team_entry.match_pool = match_pool.key();
+    team_entry.team_index = team_index;
+    team_entry.commitment = commitment;
+    team_entry.is_revealed = false;
+    team_entry.lineup = TeamLineup::default();
+    team_entry.entry_fee = match_pool.entry_fee;
+    team_entry.prize_amount = 0;
+    team_entry.is_claimed = false;
+    team_entry.bump = ctx.bumps.team_entry;
+
+    user_deposit.team_count += 1;
+
+    emit!(TeamCommittedEvent {
+        user: team_entry.user,
+        match_id: match_pool.match_id.clone(),
+        team_index,
+        commitment,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(team_index: u16)]
+pub struct CommitTeam<'info> {
+    #[account(
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"user_deposit", match_pool.match_id.as_bytes(), user.key().as_ref()],
+        bump = user_deposit.bump,
+    )]
+    pub user_deposit: Account<'info, UserDeposit>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + TeamEntry::INIT_SPACE,
+        seeds = [b"team_entry", match_pool.match_id.as_bytes(), user.key().as_ref(), &team_index.to_le_bytes()],
+        bump,
+    )]
+    pub team_entry: Account<'info, TeamEntry>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
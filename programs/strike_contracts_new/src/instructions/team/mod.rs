@@ -0,0 +1,5 @@
+pub use commit_team::*;
+mod commit_team;
+pub use reveal_team::*;
+mod reveal_team;pub use close_team_entry::*;
+mod close_team_entry;
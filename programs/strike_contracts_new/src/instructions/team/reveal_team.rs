@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, CustomError, MatchPool, MatchStatus, TeamEntry, TeamLineup, TeamRevealedEvent};
+
+// Opens a committed team once registration has closed. Teams that are never
+// revealed are not eligible for prizes.
+pub fn process_reveal_team(
+    ctx: Context<RevealTeam>,
+    team_index: u16,
+    lineup: TeamLineup,
+    salt: [u8; 32],
+) -> Result<()> {
+    let match_pool = &mut ctx.accounts.match_pool;
+    let team_entry = &mut ctx.accounts.team_entry;
+
+    match_pool.check_status(&[MatchStatus::Locked, MatchStatus::Scoring])?;
+    require!(!team_entry.is_revealed, CustomError::TeamAlreadyRevealed);
+    let commitment = lineup.commitment(&team_entry.user, &team_entry.match_pool, team_index, &salt)?;
+    require!(commitment == team_entry.commitment, CustomError::InvalidReveal);
+    require!(lineup.is_valid(), CustomError::InvalidLineup);
+
+    team_entry.is_revealed = true;
+    team_entry.lineup = lineup;
+    match_pool.revealed_count += 1;
+
+    emit!(TeamRevealedEvent {
+        user: team_entry.user,
+        match_id: match_pool.match_id.clone(),
+        team_index,
+        captain: team_entry.lineup.captain,
+        vice_captain: team_entry.lineup.vice_captain,
+        players: team_entry.lineup.players,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(team_index: u16)]
+pub struct RevealTeam<'info> {
+    #[account(
+        mut,
+        seeds = [b"match_pool", match_pool.match_id.as_bytes()],
+        bump = match_pool.bump,
+    )]
+    pub match_pool: Account<'info, MatchPool>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"team_entry", match_pool.match_id.as_bytes(), user.key().as_ref(), &team_index.to_le_bytes()],
+        bump = team_entry.bump,
+    )]
+    pub team_entry: Account<'info, TeamEntry>,
+
+    pub user: Signer<'info>,
+}
@@ -1,49 +1,51 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount};
 use crate::error::CustomError;
-use crate::state::MatchPool;
+use crate::state::{Config, MatchPool, MatchStatus, UserDeposit};
 use crate::event::DepositEvent;
 
-// Function to process a token transfer within the rollup environment
+// Hands `entries` unused entries, and the stake paid for them, to another
+// participant within the rollup. Entries backing a committed team stay put, so
+// a team can never be left in the contest without a paid entry behind it. Only
+// while registration is open, as it can change the participant count.
 pub fn process_transfer_in_rollup(
     ctx: Context<TransferInRollup>,
-    amount: u64,
+    entries: u32,
 ) -> Result<()> {
     let match_pool = &mut ctx.accounts.match_pool;
-    let sender = &ctx.accounts.sender;
+    match_pool.check_status(&[MatchStatus::Open])?;
+
     let receiver_key = ctx.accounts.receiver.key();
-    
-    // Find the sender's deposit record
-    let sender_key = sender.key();
-    let sender_deposit_opt = match_pool.deposits.iter_mut().find(|d| d.user == sender_key);
-    
-    // Ensure sender has enough balance
-    let sender_deposit = if let Some(deposit) = sender_deposit_opt {
-        deposit
-    } else {
-        return Err(error!(CustomError::InsufficientBalance));
-    };
-    
-    if sender_deposit.amount < amount {
-        return Err(error!(CustomError::InsufficientBalance));
-    }
-    
+    let sender_deposit = &mut ctx.accounts.sender_deposit;
+    let receiver_deposit = &mut ctx.accounts.receiver_deposit;
+
+    require!(entries > 0, CustomError::InvalidEntryCount);
+    require!(entries <= sender_deposit.unused_entries(), CustomError::EntriesInUse);
+
+    let receiver_entries = receiver_deposit.entries.checked_add(entries)
+        .ok_or(error!(CustomError::ArithmeticOverflow))?;
+    require!(receiver_entries <= match_pool.max_entries_per_user, CustomError::EntryLimitExceeded);
+
+    let amount = sender_deposit.stake_for(entries, match_pool.entry_fee);
+    let receiver_was_empty = receiver_deposit.entries == 0;
+
     // Deduct from sender
     sender_deposit.amount = sender_deposit.amount.checked_sub(amount)
         .ok_or(error!(CustomError::InsufficientBalance))?;
-    
-    // Add to receiver (create new deposit record if needed)
-    if let Some(receiver_deposit) = match_pool.deposits.iter_mut().find(|d| d.user == receiver_key) {
-        receiver_deposit.amount = receiver_deposit.amount.checked_add(amount)
-            .ok_or(error!(CustomError::TokenTransferError))?;
-    } else {
-        // Create new deposit record for receiver
-        match_pool.deposits.push(crate::state::UserDeposit {
-            user: receiver_key,
-            amount,
-        });
+    sender_deposit.entries -= entries;
+
+    // Add to receiver
+    receiver_deposit.amount = receiver_deposit.amount.checked_add(amount)
+        .ok_or(error!(CustomError::TokenTransferError))?;
+    receiver_deposit.entries = receiver_entries;
+
+    // Participants are deposits holding at least one entry
+    if sender_deposit.entries == 0 {
+        match_pool.participant_count -= 1;
     }
-    
+    if receiver_was_empty {
+        match_pool.participant_count += 1;
+    }
+
     // Emit event for the token transfer
     emit!(DepositEvent {
         user: receiver_key,
@@ -59,7 +61,8 @@ pub struct TransferInRollup<'info> {
     #[account(mut)]
     pub sender: Signer<'info>,
     
-    /// CHECK: Receiver can be any account
+    /// CHECK: Receiver can be any account other than the sender
+    #[account(constraint = receiver.key() != sender.key() @ CustomError::InvalidReceiver)]
     pub receiver: AccountInfo<'info>,
     
     #[account(
@@ -68,21 +71,28 @@ pub struct TransferInRollup<'info> {
         bump = match_pool.bump,
     )]
     pub match_pool: Account<'info, MatchPool>,
-    
-    pub system_program: Program<'info, System>,
-}
 
-// Structure for user token account delegation
-#[derive(Accounts)]
-pub struct DelegateUserTokenAccount<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(&match_pool) @ CustomError::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+
     #[account(
         mut,
-        constraint = user_token_account.owner == user.key() @ CustomError::Unauthorized
+        seeds = [b"user_deposit", match_pool.match_id.as_bytes(), sender.key().as_ref()],
+        bump = sender_deposit.bump,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub sender_deposit: Account<'info, UserDeposit>,
+
+    /// The receiver must already hold a deposit record in this match
+    #[account(
+        mut,
+        seeds = [b"user_deposit", match_pool.match_id.as_bytes(), receiver.key().as_ref()],
+        bump = receiver_deposit.bump,
+    )]
+    pub receiver_deposit: Account<'info, UserDeposit>,
     
-    pub token_program: Program<'info, Token>,
-}
\ No newline at end of file
+    pub system_program: Program<'info, System>,
+}
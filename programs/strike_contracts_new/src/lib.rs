@@ -7,6 +7,8 @@ pub mod state;
 pub mod instructions;
 pub mod error;
 pub mod event;
+pub mod merkle;
+pub mod utils;
 
 use crate::state::*;
 use crate::error::*;
@@ -21,13 +23,69 @@ pub mod strike_contracts_new {
 
     use super::*;
 
+    // Config instructions
+    pub fn initialize_config(ctx: Context<InitializeConfig>, treasury: Pubkey) -> Result<()> {
+        process_initialize_config(ctx, treasury)
+    }
+
+    pub fn add_operator(ctx: Context<ManageOperators>, operator: Pubkey) -> Result<()> {
+        process_add_operator(ctx, operator)
+    }
+
+    pub fn remove_operator(ctx: Context<ManageOperators>, operator: Pubkey) -> Result<()> {
+        process_remove_operator(ctx, operator)
+    }
+
+    pub fn set_treasury(ctx: Context<SetTreasury>, treasury: Pubkey) -> Result<()> {
+        process_set_treasury(ctx, treasury)
+    }
+
+    pub fn transfer_super_admin(ctx: Context<TransferSuperAdmin>, new_super_admin: Pubkey) -> Result<()> {
+        process_transfer_super_admin(ctx, new_super_admin)
+    }
+
+    pub fn accept_super_admin(ctx: Context<AcceptSuperAdmin>) -> Result<()> {
+        process_accept_super_admin(ctx)
+    }
+
+    pub fn set_oracle(ctx: Context<SetOracle>, oracle: Pubkey) -> Result<()> {
+        process_set_oracle(ctx, oracle)
+    }
+
+    pub fn set_challenge_period(ctx: Context<SetChallengePeriod>, challenge_period: i64) -> Result<()> {
+        process_set_challenge_period(ctx, challenge_period)
+    }
+
+    pub fn set_allowed_mint(
+        ctx: Context<SetAllowedMint>,
+        min_entry_fee: u64,
+        max_entry_fee: u64,
+    ) -> Result<()> {
+        process_set_allowed_mint(ctx, min_entry_fee, max_entry_fee)
+    }
+
+    pub fn remove_allowed_mint(ctx: Context<RemoveAllowedMint>) -> Result<()> {
+        process_remove_allowed_mint(ctx)
+    }
+
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+        process_set_guardian(ctx, guardian)
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool, exits_when_paused: bool) -> Result<()> {
+        process_set_paused(ctx, paused, exits_when_paused)
+    }
+
     // Admin instructions
     pub fn initialize(
         ctx: Context<Initialize>, 
         match_id: String, 
-        registration_end_time: i64
+        registration_end_time: i64,
+        match_start_time: i64,
+        fee_bps: u16,
+        entry_rules: EntryRules
     ) -> Result<()> {
-        process_initialize(ctx, match_id, registration_end_time)
+        process_initialize(ctx, match_id, registration_end_time, match_start_time, fee_bps, entry_rules)
     }
 
     pub fn end_match(ctx: Context<EndMatch>) -> Result<()> {
@@ -36,29 +94,179 @@ pub mod strike_contracts_new {
 
     pub fn distribute_prizes<'a, 'b, 'c, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, DistributePrizes<'info>>, 
-        prize_distributions: Vec<PrizeDistribution>
+        start: u32,
+        winners: Vec<RankedTeam>,
+        proofs: Vec<Vec<[u8; 32]>>,
     ) -> Result<()> {
-        process_distribute_prizes(ctx, prize_distributions)
+        process_distribute_prizes(ctx, start, winners, proofs)
     }
 
     pub fn close_match_pool(ctx: Context<CloseMatchPool>) -> Result<()> {
         process_close_match_pool(ctx)
     }
 
+    pub fn cancel_match(ctx: Context<CancelMatch>) -> Result<()> {
+        process_cancel_match(ctx)
+    }
+
+    pub fn set_prize_allocations<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SetPrizeAllocations<'info>>,
+        allocations: Vec<PrizeDistribution>,
+        claim_expiry: i64
+    ) -> Result<()> {
+        process_set_prize_allocations(ctx, allocations, claim_expiry)
+    }
+
+    pub fn finalize_with_merkle_root(
+        ctx: Context<FinalizeWithMerkleRoot>,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+        claim_expiry: i64
+    ) -> Result<()> {
+        process_finalize_with_merkle_root(ctx, merkle_root, total_amount, claim_expiry)
+    }
+
+    pub fn finalize_prize_claims(ctx: Context<FinalizePrizeClaims>) -> Result<()> {
+        process_finalize_prize_claims(ctx)
+    }
+
+    pub fn set_prize_table(
+        ctx: Context<SetPrizeTable>,
+        prize_table: Vec<PrizeTier>,
+        dust_sink: DustSink
+    ) -> Result<()> {
+        process_set_prize_table(ctx, prize_table, dust_sink)
+    }
+
+    pub fn set_strict_distribution(ctx: Context<SetStrictDistribution>, strict: bool) -> Result<()> {
+        process_set_strict_distribution(ctx, strict)
+    }
+
+    pub fn post_results(ctx: Context<PostResults>, results_hash: [u8; 32], winner_count: u32) -> Result<()> {
+        process_post_results(ctx, results_hash, winner_count)
+    }
+
+    pub fn set_guaranteed_amount(
+        ctx: Context<SetGuaranteedAmount>,
+        guaranteed_amount: u64,
+        sponsor: Pubkey,
+    ) -> Result<()> {
+        process_set_guaranteed_amount(ctx, guaranteed_amount, sponsor)
+    }
+
+    pub fn update_schedule(
+        ctx: Context<UpdateSchedule>,
+        registration_end_time: i64,
+        match_start_time: i64,
+    ) -> Result<()> {
+        process_update_schedule(ctx, registration_end_time, match_start_time)
+    }
+
+    pub fn set_pool_paused(ctx: Context<SetPoolPaused>, paused: bool) -> Result<()> {
+        process_set_pool_paused(ctx, paused)
+    }
+
     // Deposit instructions
-    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
-        process_deposit(ctx, amount)
+    pub fn enter_contest(ctx: Context<EnterContest>, entries: u32) -> Result<()> {
+        process_enter_contest(ctx, entries)
+    }
+
+    pub fn withdraw_deposit(ctx: Context<WithdrawDeposit>, entries: u32) -> Result<()> {
+        process_withdraw_deposit(ctx, entries)
+    }
+
+    pub fn deposit_sol(ctx: Context<DepositSol>, entries: u32) -> Result<()> {
+        process_deposit_sol(ctx, entries)
+    }
+
+    pub fn finalize_registration(ctx: Context<FinalizeRegistration>) -> Result<()> {
+        process_finalize_registration(ctx)
+    }
+
+    pub fn close_user_deposit(ctx: Context<CloseUserDeposit>) -> Result<()> {
+        process_close_user_deposit(ctx)
+    }
+
+    // Team instructions
+    pub fn commit_team(ctx: Context<CommitTeam>, team_index: u16, commitment: [u8; 32]) -> Result<()> {
+        process_commit_team(ctx, team_index, commitment)
+    }
+
+    pub fn reveal_team(
+        ctx: Context<RevealTeam>,
+        team_index: u16,
+        lineup: TeamLineup,
+        salt: [u8; 32]
+    ) -> Result<()> {
+        process_reveal_team(ctx, team_index, lineup, salt)
+    }
+
+    pub fn close_team_entry(ctx: Context<CloseTeamEntry>, team_index: u16) -> Result<()> {
+        process_close_team_entry(ctx, team_index)
+    }
+
+    // Scoring instructions
+    pub fn submit_scores(ctx: Context<SubmitScores>, points: Vec<PlayerPoints>, finalize: bool) -> Result<()> {
+        process_submit_scores(ctx, points, finalize)
+    }
+
+    pub fn compute_team_score(ctx: Context<ComputeTeamScore>, user: Pubkey, team_index: u16) -> Result<()> {
+        process_compute_team_score(ctx, user, team_index)
+    }
+
+    pub fn rank_and_settle<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, RankAndSettle<'info>>
+    ) -> Result<()> {
+        process_rank_and_settle(ctx)
+    }
+
+    // Dispute instructions
+    pub fn raise_dispute(ctx: Context<RaiseDispute>, reason: [u8; 32]) -> Result<()> {
+        process_raise_dispute(ctx, reason)
+    }
+
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, upheld: bool) -> Result<()> {
+        process_resolve_dispute(ctx, upheld)
+    }
+
+    pub fn void_results(ctx: Context<VoidResults>) -> Result<()> {
+        process_void_results(ctx)
+    }
+
+    // Sponsor instructions
+    pub fn sponsor_top_up(ctx: Context<SponsorTopUp>, amount: u64) -> Result<()> {
+        process_sponsor_top_up(ctx, amount)
+    }
+
+    pub fn refund_sponsor(ctx: Context<RefundSponsor>) -> Result<()> {
+        process_refund_sponsor(ctx)
+    }
+
+    // Refund instructions
+    pub fn refund_deposit(ctx: Context<RefundDeposit>) -> Result<()> {
+        process_refund_deposit(ctx)
+    }
+
+    // Claim instructions
+    pub fn claim_prize(ctx: Context<ClaimPrize>, team_index: u16) -> Result<()> {
+        process_claim_prize(ctx, team_index)
+    }
+
+    pub fn claim_with_proof(ctx: Context<ClaimWithProof>, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        process_claim_with_proof(ctx, amount, proof)
     }
     
     // Token transfer instruction for rollup environment
-    pub fn transfer_in_rollup(ctx: Context<TransferInRollup>, amount: u64) -> Result<()> {
-        process_transfer_in_rollup(ctx, amount)
+    pub fn transfer_in_rollup(ctx: Context<TransferInRollup>, entries: u32) -> Result<()> {
+        process_transfer_in_rollup(ctx, entries)
     }
     
     // MagicBlock instructions
     
     /// Delegate a match pool account to the delegation program
     pub fn delegate_match_pool(ctx: Context<DelegateMatchPool>) -> Result<()> {
+        // Only live pools are moved to the rollup
+        ctx.accounts.match_pool.check_status(&[MatchStatus::Open, MatchStatus::Locked])?;
         ctx.accounts.delegate_match_pool(
             &ctx.accounts.admin,
             &[b"match_pool", ctx.accounts.match_pool.match_id.as_bytes()],
@@ -72,6 +280,7 @@ pub mod strike_contracts_new {
 
     /// Delegate a pool token account to the delegation program
     pub fn delegate_pool_token_account(ctx: Context<DelegatePoolTokenAccount>) -> Result<()> {
+        ctx.accounts.match_pool.check_status(&[MatchStatus::Open, MatchStatus::Locked])?;
         ctx.accounts.delegate_pool_token_account(
             &ctx.accounts.admin,
             &[b"pool_token", ctx.accounts.match_pool.match_id.as_bytes()],
@@ -85,6 +294,7 @@ pub mod strike_contracts_new {
 
     /// Delegate a user's token account to the delegation program
     pub fn delegate_user_token_account(ctx: Context<DelegateUserTokenAccount>) -> Result<()> {
+        ctx.accounts.match_pool.check_status(&[MatchStatus::Open, MatchStatus::Locked])?;
         ctx.accounts.delegate_user_token_account(
             &ctx.accounts.user,
             &[],  // No seeds needed for user token accounts as they're not PDAs
@@ -98,6 +308,7 @@ pub mod strike_contracts_new {
 
     /// Delegate a user deposit account to the delegation program
     pub fn delegate_user_deposit(ctx: Context<DelegateUserDeposit>) -> Result<()> {
+        ctx.accounts.match_pool.check_status(&[MatchStatus::Open, MatchStatus::Locked])?;
         ctx.accounts.delegate_user_deposit(
             &ctx.accounts.user,
             &[b"user_deposit", ctx.accounts.match_pool.match_id.as_bytes(), ctx.accounts.user.key().as_ref()],
@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use std::collections::HashSet;
+
+use crate::error::CustomError;
+
+// Domain separation so a leaf can never be passed off as an inner node
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+const WINNER_LEAF_PREFIX: &[u8] = &[2];
+
+/// Hash of a single `(user, amount)` prize leaf.
+pub fn leaf_hash(user: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, user.as_ref(), &amount.to_le_bytes()]).to_bytes()
+}
+
+/// Hash of a `(position, user, team_index, rank)` leaderboard leaf, position 0 being
+/// first place. `post_results` commits the root of these for `distribute_prizes`.
+pub fn winner_leaf_hash(position: u32, user: &Pubkey, team_index: u16, rank: u16) -> [u8; 32] {
+    hashv(&[
+        WINNER_LEAF_PREFIX,
+        &position.to_le_bytes(),
+        user.as_ref(),
+        &team_index.to_le_bytes(),
+        &rank.to_le_bytes(),
+    ]).to_bytes()
+}
+
+/// Hash of two sibling nodes. Siblings are sorted so proofs carry no path bits.
+pub fn node_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+/// Checks that `leaf` is part of the tree with the given `root`.
+pub fn verify_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| node_hash(&node, sibling));
+    computed == *root
+}
+
+/// Off-chain helper that builds the prize tree with the same leaf encoding
+/// `claim_with_proof` verifies against.
+pub struct MerkleTree {
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    /// Receipts are kept per wallet, so each user gets a single leaf holding
+    /// the sum of their prizes. Duplicate users are rejected.
+    pub fn new(leaves: &[(Pubkey, u64)]) -> Result<Self> {
+        let mut users = HashSet::new();
+        for (user, _) in leaves {
+            require!(users.insert(*user), CustomError::DuplicateWinner);
+        }
+
+        Ok(Self::from_leaves(
+            leaves.iter().map(|(user, amount)| leaf_hash(user, *amount)).collect(),
+        ))
+    }
+
+    /// Builds a tree over leaves that are already hashed
+    pub fn from_leaves(leaves: Vec<[u8; 32]>) -> Self {
+        let mut levels = vec![leaves];
+
+        while levels[levels.len() - 1].len() > 1 {
+            // An odd node out is carried up to the next level unchanged
+            let next = levels[levels.len() - 1]
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => node_hash(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Self { levels }
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels
+            .last()
+            .and_then(|level| level.first())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Sibling hashes from the leaf at `index` up to the root.
+    pub fn proof(&self, mut index: usize) -> Vec<[u8; 32]> {
+        let mut proof = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        proof
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prize_leaves(count: usize) -> Vec<(Pubkey, u64)> {
+        (0..count).map(|i| (Pubkey::new_unique(), 1_000 * (i as u64 + 1))).collect()
+    }
+
+    fn assert_all_proofs_verify(leaves: &[(Pubkey, u64)]) {
+        let tree = MerkleTree::new(leaves).unwrap();
+        for (index, (user, amount)) in leaves.iter().enumerate() {
+            assert!(verify_proof(&tree.proof(index), &tree.root(), leaf_hash(user, *amount)));
+        }
+    }
+
+    #[test]
+    fn single_leaf_is_its_own_root() {
+        let leaves = prize_leaves(1);
+        let tree = MerkleTree::new(&leaves).unwrap();
+
+        assert_eq!(tree.root(), leaf_hash(&leaves[0].0, leaves[0].1));
+        assert!(tree.proof(0).is_empty());
+        assert_all_proofs_verify(&leaves);
+    }
+
+    #[test]
+    fn proofs_verify_for_even_and_odd_leaf_counts() {
+        for count in 2..=9 {
+            assert_all_proofs_verify(&prize_leaves(count));
+        }
+    }
+
+    #[test]
+    fn empty_tree_has_zero_root() {
+        let tree = MerkleTree::new(&[]).unwrap();
+
+        assert_eq!(tree.root(), [0u8; 32]);
+        assert!(tree.proof(0).is_empty());
+    }
+
+    #[test]
+    fn proof_rejects_wrong_amount() {
+        let leaves = prize_leaves(5);
+        let tree = MerkleTree::new(&leaves).unwrap();
+
+        let (user, amount) = leaves[2];
+        assert!(!verify_proof(&tree.proof(2), &tree.root(), leaf_hash(&user, amount + 1)));
+    }
+
+    #[test]
+    fn winner_proofs_are_bound_to_their_position() {
+        let winners: Vec<(Pubkey, u16, u16)> =
+            (0..5).map(|i| (Pubkey::new_unique(), 0, i as u16 + 1)).collect();
+        let leaves = winners.iter().enumerate()
+            .map(|(position, (user, team_index, rank))| {
+                winner_leaf_hash(position as u32, user, *team_index, *rank)
+            })
+            .collect();
+        let tree = MerkleTree::from_leaves(leaves);
+
+        let (user, team_index, rank) = winners[3];
+        assert!(verify_proof(&tree.proof(3), &tree.root(), winner_leaf_hash(3, &user, team_index, rank)));
+        assert!(!verify_proof(&tree.proof(3), &tree.root(), winner_leaf_hash(2, &user, team_index, rank)));
+    }
+
+    #[test]
+    fn duplicate_users_are_rejected() {
+        let user = Pubkey::new_unique();
+
+        assert!(MerkleTree::new(&[(user, 1_000), (user, 2_000)]).is_err());
+    }
+}
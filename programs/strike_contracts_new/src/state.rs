@@ -1,15 +1,101 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_spl::{token::spl_token, token_2022::spl_token_2022};
+
+use crate::{error::CustomError, event::MatchStatusChangedEvent};
+
+#[account]
+#[derive(Default)]
+pub struct Config {
+    pub super_admin: Pubkey,
+    pub pending_super_admin: Pubkey,
+    pub operators: Vec<Pubkey>,
+    pub treasury: Pubkey,
+    pub oracle: Pubkey,
+    pub challenge_period: i64,
+    pub guardian: Pubkey,
+    pub paused: bool,
+    pub exits_when_paused: bool,
+    pub bump: u8,
+}
+
+impl Config {
+    pub const MAX_OPERATORS: usize = 10;
+    /// Challenge window a new config starts with, in seconds
+    pub const DEFAULT_CHALLENGE_PERIOD: i64 = 24 * 60 * 60;
+
+    pub const INIT_SPACE: usize =
+        32 +                          // super_admin: Pubkey
+        32 +                          // pending_super_admin: Pubkey
+        4 + (Self::MAX_OPERATORS * 32) + // operators: Vec<Pubkey> (max 10 operators)
+        32 +                          // treasury: Pubkey
+        32 +                          // oracle: Pubkey
+        8 +                           // challenge_period: i64
+        32 +                          // guardian: Pubkey
+        1 +                           // paused: bool
+        1 +                           // exits_when_paused: bool
+        1;                            // bump: u8
+
+    /// The super-admin can always act as an operator
+    pub fn is_operator(&self, key: &Pubkey) -> bool {
+        self.super_admin == *key || self.operators.contains(key)
+    }
+
+    /// The guardian holds the emergency switch, and so does the super-admin
+    pub fn can_pause(&self, key: &Pubkey) -> bool {
+        self.super_admin == *key || self.guardian == *key
+    }
+
+    pub fn is_paused(&self, match_pool: &MatchPool) -> bool {
+        self.paused || match_pool.paused
+    }
+
+    /// Refunds and claims can be left open during a pause so funds are never stuck
+    pub fn allows_exits(&self, match_pool: &MatchPool) -> bool {
+        !self.is_paused(match_pool) || self.exits_when_paused
+    }
+}
 
 #[account]
 #[derive(Default)]
 pub struct MatchPool {
     pub admin: Pubkey,
     pub match_id: String,
+    pub mint: Pubkey,
     pub registration_end_time: i64,
+    pub match_start_time: i64,
     pub total_deposited: u64,
-    pub is_active: bool,
-    pub is_finalized: bool,
-    pub deposits: Vec<UserDeposit>,
+    pub entry_fee: u64,
+    pub max_entries_per_user: u32,
+    pub max_participants: u32,
+    pub min_participants: u32,
+    pub participant_count: u32,
+    pub revealed_count: u32,
+    pub scored_count: u32,
+    pub fee_bps: u16,
+    pub fee_collected: u64,
+    pub treasury: Pubkey,
+    pub sponsor: Pubkey,
+    pub sponsored_amount: u64,
+    pub guaranteed_amount: u64,
+    pub rollover_amount: u64,
+    pub status: MatchStatus,
+    pub total_allocated: u64,
+    pub total_claimed: u64,
+    pub claim_expiry: i64,
+    pub leaderboard_settled: bool,
+    pub prize_table: Vec<PrizeTier>,
+    pub dust_sink: DustSink,
+    pub winner_count: u32,
+    pub distribution_cursor: u32,
+    pub distributed_so_far: u64,
+    pub first_place: Pubkey,
+    pub strict_distribution: bool,
+    pub results_hash: [u8; 32],
+    pub challenge_end_time: i64,
+    pub open_disputes: u32,
+    pub paused: bool,
+    pub merkle_root: [u8; 32],
     pub bump: u8,
     pub token_bump: u8,
 }
@@ -18,17 +104,768 @@ impl MatchPool {
     pub const INIT_SPACE: usize =
         32 +                          // admin: Pubkey
         4 + 50 +                      // match_id: String (max 50 chars)
+        32 +                          // mint: Pubkey
         8 +                           // registration_end_time: i64
+        8 +                           // match_start_time: i64
         8 +                           // total_deposited: u64
-        1 +                           // is_active: bool
-        1 +                           // is_finalized: bool
-        4 + (50 * (32 + 8)) +         // deposits: Vec<UserDeposit> (max 50 users)
+        8 +                           // entry_fee: u64
+        4 +                           // max_entries_per_user: u32
+        4 +                           // max_participants: u32
+        4 +                           // min_participants: u32
+        4 +                           // participant_count: u32
+        4 +                           // revealed_count: u32
+        4 +                           // scored_count: u32
+        2 +                           // fee_bps: u16
+        8 +                           // fee_collected: u64
+        32 +                          // treasury: Pubkey
+        32 +                          // sponsor: Pubkey
+        8 +                           // sponsored_amount: u64
+        8 +                           // guaranteed_amount: u64
+        8 +                           // rollover_amount: u64
+        1 +                           // status: MatchStatus
+        8 +                           // total_allocated: u64
+        8 +                           // total_claimed: u64
+        8 +                           // claim_expiry: i64
+        1 +                           // leaderboard_settled: bool
+        4 + (Self::MAX_PRIZE_TIERS * PrizeTier::INIT_SPACE) + // prize_table: Vec<PrizeTier> (max 10 tiers)
+        DustSink::INIT_SPACE +        // dust_sink: DustSink
+        4 +                           // winner_count: u32
+        4 +                           // distribution_cursor: u32
+        8 +                           // distributed_so_far: u64
+        32 +                          // first_place: Pubkey
+        1 +                           // strict_distribution: bool
+        32 +                          // results_hash: [u8; 32]
+        8 +                           // challenge_end_time: i64
+        4 +                           // open_disputes: u32
+        1 +                           // paused: bool
+        32 +                          // merkle_root: [u8; 32]
         1 +                           // bump: u8
         1;                            // token_bump: u8
+
+    /// Also the longest a single PDA seed can be, so it is below the space reserved
+    pub const MAX_MATCH_ID_LEN: usize = 32;
+    pub const MAX_FEE_BPS: u16 = 10_000;
+    pub const MAX_PRIZE_TIERS: usize = 10;
+    /// How long winners have to claim after a permissionless `rank_and_settle`
+    pub const SETTLEMENT_CLAIM_WINDOW: i64 = 30 * 24 * 60 * 60;
+    /// `rank_and_settle` reads the whole leaderboard in one transaction, so it
+    /// only settles contests whose teams fit in it through a lookup table next
+    /// to the instruction's own accounts (64 account locks at most). Larger
+    /// contests settle with `distribute_prizes` or a Merkle root.
+    pub const MAX_SETTLEMENT_TEAMS: u32 = 50;
+
+    /// Registration closes by itself at the deadline, so an open pool reads as
+    /// locked from then on even before the change has been written back
+    pub fn current_status(&self, now: i64) -> MatchStatus {
+        match self.status {
+            MatchStatus::Open if now >= self.registration_end_time => MatchStatus::Locked,
+            status => status,
+        }
+    }
+
+    /// Fails unless the pool is currently in one of the `allowed` statuses
+    pub fn check_status(&self, allowed: &[MatchStatus]) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(allowed.contains(&self.current_status(now)), CustomError::InvalidMatchStatus);
+        Ok(())
+    }
+
+    /// Moves the pool to `next` if the lifecycle allows it, emitting an event per step
+    pub fn transition_to(&mut self, next: MatchStatus) -> Result<()> {
+        self.sync_status()?;
+        require!(self.status.can_transition_to(next), CustomError::InvalidStatusTransition);
+        self.set_status(next);
+
+        Ok(())
+    }
+
+    /// Writes back a status change the clock has already made, such as registration closing
+    pub fn sync_status(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let current = self.current_status(now);
+
+        if current != self.status {
+            self.set_status(current);
+        }
+
+        Ok(())
+    }
+
+    fn set_status(&mut self, next: MatchStatus) {
+        emit!(MatchStatusChangedEvent {
+            match_id: self.match_id.clone(),
+            from: self.status,
+            to: next,
+        });
+        self.status = next;
+    }
+
+    /// Whether the contest is denominated in wrapped SOL, under either token program
+    pub fn is_native(&self) -> bool {
+        self.mint == spl_token::native_mint::ID || self.mint == spl_token_2022::native_mint::ID
+    }
+
+    pub fn has_enough_participants(&self) -> bool {
+        self.participant_count >= self.min_participants
+    }
+
+    pub fn results_posted(&self) -> bool {
+        self.results_hash != [0u8; 32]
+    }
+
+    /// Whether `distribute_prizes` owns the settlement, which stays true after
+    /// voided results once a page has been paid
+    pub fn distribution_started(&self) -> bool {
+        self.winner_count > 0 || self.distribution_cursor > 0
+    }
+
+    /// Posted results can only be voided while nothing has been paid against them
+    pub fn can_void_results(&self) -> bool {
+        self.results_posted()
+            && self.total_claimed == 0
+            && (self.winner_count == 0 || self.distribution_cursor < self.winner_count)
+    }
+
+    /// Prizes can only leave the pool once posted results have survived the challenge window
+    pub fn payouts_unlocked(&self, now: i64) -> bool {
+        self.results_posted() && self.open_disputes == 0 && now >= self.challenge_end_time
+    }
+
+    /// Throws away posted results, after an upheld dispute or by the super-admin, so
+    /// corrected ones can be posted.
+    /// A Merkle root is part of the results. Allocations on team entries stay, to be
+    /// corrected or zeroed with `set_prize_allocations` before posting again.
+    pub fn void_results(&mut self) {
+        self.results_hash = [0u8; 32];
+        self.challenge_end_time = 0;
+        self.winner_count = 0;
+
+        if self.merkle_root != [0u8; 32] {
+            self.merkle_root = [0u8; 32];
+            self.total_allocated = 0;
+        }
+    }
+
+    /// Deposits left for prizes once the platform fee has been taken, plus dust
+    /// other pools rolled over into this one
+    pub fn entry_pot(&self) -> u64 {
+        self.total_deposited.saturating_sub(self.fee_collected).saturating_add(self.rollover_amount)
+    }
+
+    /// The entry pot, topped up from sponsor funds to the guaranteed amount if it falls short
+    pub fn prize_pool(&self) -> u64 {
+        let entry_pot = self.entry_pot();
+
+        if self.guaranteed_amount <= entry_pot {
+            entry_pot
+        } else {
+            self.guaranteed_amount.min(entry_pot.saturating_add(self.sponsored_amount))
+        }
+    }
+
+    pub fn is_guarantee_funded(&self) -> bool {
+        self.entry_pot().saturating_add(self.sponsored_amount) >= self.guaranteed_amount
+    }
+
+    /// What winners receive out of the prize pool: paid prizes and prizes left to
+    /// claim, plus the rounding dust when it goes to first place
+    pub fn prizes_awarded(&self) -> u64 {
+        let awarded = self.distributed_so_far.saturating_add(self.total_allocated);
+
+        match self.dust_sink {
+            DustSink::FirstPlace if self.winner_count > 0 => {
+                awarded.max(self.table_pot(self.winner_count as usize))
+            }
+            _ => awarded,
+        }
+    }
+
+    /// Sponsor funds the winners didn't receive, all of them if the match was
+    /// cancelled. Entries pay for prizes first, the sponsor only tops them up.
+    pub fn unused_sponsorship(&self) -> u64 {
+        if self.status == MatchStatus::Cancelled {
+            return self.sponsored_amount;
+        }
+        let used = self.prizes_awarded().saturating_sub(self.entry_pot());
+        self.sponsored_amount.saturating_sub(used)
+    }
+
+    /// Prize for each team of a leaderboard slice starting at `first_position`
+    /// (0 is first place), given as competition ranks, best first (e.g.
+    /// `[1, 2, 2, 4]`). A tier's share is split evenly between its ranks and tied
+    /// teams split the prizes of the places they cover evenly. What rounding
+    /// leaves over, measured against `table_pot`, is dust for the `dust_sink`.
+    pub fn prize_amounts(&self, first_position: usize, ranks: &[u16]) -> Vec<u64> {
+        let prize_pool = self.prize_pool() as u128;
+        let mut place_amounts = vec![0u128; ranks.len()];
+
+        for tier in self.prize_table.iter() {
+            let tier_pot = prize_pool * tier.bps as u128 / Self::MAX_FEE_BPS as u128;
+            let share = tier_pot / (tier.end_rank - tier.start_rank + 1) as u128;
+
+            for rank in tier.start_rank..=tier.end_rank {
+                let position = rank as usize - 1;
+                if position < first_position {
+                    continue;
+                }
+                if let Some(amount) = place_amounts.get_mut(position - first_position) {
+                    *amount = share;
+                }
+            }
+        }
+
+        let mut amounts = vec![0u64; ranks.len()];
+        let mut start = 0;
+        while start < ranks.len() {
+            let end = start + ranks[start..].iter().take_while(|&&rank| rank == ranks[start]).count();
+            let tied_share = place_amounts[start..end].iter().sum::<u128>() / (end - start) as u128;
+            amounts[start..end].fill(tied_share as u64);
+            start = end;
+        }
+
+        amounts
+    }
+
+    /// What the prize table hands out over the first `places` places before
+    /// tier shares and ties are rounded down
+    pub fn table_pot(&self, places: usize) -> u64 {
+        let prize_pool = self.prize_pool() as u128;
+
+        self.prize_table.iter()
+            .map(|tier| {
+                let tier_pot = prize_pool * tier.bps as u128 / Self::MAX_FEE_BPS as u128;
+                let ranks = (tier.end_rank - tier.start_rank + 1) as u128;
+                let covered = (tier.end_rank as usize).min(places)
+                    .saturating_sub(tier.start_rank as usize - 1) as u128;
+                tier_pot * covered / ranks
+            })
+            .sum::<u128>() as u64
+    }
+
+    /// Whether a token account can receive whatever is left in the pool after a
+    /// claim-based settlement. First-place dust is already in the winner's
+    /// allocation by then, so only unclaimed prizes remain for the treasury. A
+    /// rollover is checked against the receiving pool by `utils::dust_destination`.
+    pub fn is_sweep_account(&self, token_account: &Pubkey) -> bool {
+        match self.dust_sink {
+            DustSink::Rollover(_) => true,
+            _ => *token_account == self.treasury,
+        }
+    }
+
+    /// Whether a token account can receive the dust left once `distribute_prizes` is done
+    pub fn is_dust_account(&self, token_account: &Pubkey, owner: &Pubkey) -> bool {
+        match self.dust_sink {
+            DustSink::FirstPlace => *owner == self.first_place,
+            _ => self.is_sweep_account(token_account),
+        }
+    }
+
+    /// Dust can only join a pool whose prizes have not been worked out yet
+    pub fn accepts_rollover(&self, now: i64) -> bool {
+        matches!(self.current_status(now), MatchStatus::Open | MatchStatus::Locked)
+    }
+
+    pub fn pool_token_address(&self) -> Result<Pubkey> {
+        Pubkey::create_program_address(
+            &[b"pool_token", self.match_id.as_bytes(), &[self.token_bump]],
+            &crate::ID,
+        )
+        .map_err(|_| error!(CustomError::InvalidDustAccount))
+    }
+
+    /// A valid leaderboard slice starts a new rank at `first_position` and every
+    /// following team either ties the team above it or is ranked by its position.
+    /// Tied teams therefore can never be split across two slices.
+    pub fn is_valid_ranking(first_position: usize, ranks: &[u16]) -> bool {
+        ranks.iter().enumerate().all(|(i, &rank)| match i {
+            0 => rank as usize == first_position + 1,
+            _ => rank == ranks[i - 1] || rank as usize == first_position + i + 1,
+        })
+    }
 }
 
+/// Lifecycle of a match pool, only ever changed through `MatchPool::transition_to`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum MatchStatus {
+    /// Registration is open: users can enter, withdraw and commit teams
+    #[default]
+    Open,
+    /// Registration has closed and the match is in play, teams get revealed
+    Locked,
+    /// The match has ended and the fee is taken, results are being settled
+    Scoring,
+    /// Settlement is on hold while results are disputed
+    Disputed,
+    /// Every prize has been paid or swept
+    Settled,
+    /// Deposits are refunded in full
+    Cancelled,
+    /// The pool's token account has been closed, the pool itself stays so its id is never reused
+    Closed,
+}
+
+impl MatchStatus {
+    pub fn can_transition_to(self, next: MatchStatus) -> bool {
+        use MatchStatus::*;
+
+        matches!(
+            (self, next),
+            (Open, Locked)
+                | (Open, Cancelled)
+                | (Locked, Scoring)
+                | (Locked, Cancelled)
+                | (Scoring, Disputed)
+                | (Disputed, Scoring)
+                | (Scoring, Settled)
+                | (Settled, Closed)
+                | (Cancelled, Closed)
+        )
+    }
+}
+
+/// Where rounding dust ends up once a pool is settled. `FirstPlace` only ever
+/// receives the rounding remainder; shares the prize table never hands out go
+/// to the treasury.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DustSink {
+    #[default]
+    Treasury,
+    FirstPlace,
+    /// A future contest's match pool, whose prize pool the dust joins
+    Rollover(Pubkey),
+}
+
+impl DustSink {
+    pub const INIT_SPACE: usize = 1 + 32;
+}
+
+/// Share of the prize pool, in basis points, split between ranks
+/// `start_rank..=end_rank` (1 is first place)
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct PrizeTier {
+    pub start_rank: u16,
+    pub end_rank: u16,
+    pub bps: u16,
+}
+
+impl PrizeTier {
+    pub const INIT_SPACE: usize =
+        2 +                           // start_rank: u16
+        2 +                           // end_rank: u16
+        2;                            // bps: u16
+
+    /// Tiers must be non-empty, in rank order, non-overlapping and pay out at most 100%
+    pub fn is_valid_table(tiers: &[PrizeTier]) -> bool {
+        let mut next_rank = 1;
+        let mut total_bps: u32 = 0;
+
+        for tier in tiers.iter() {
+            if tier.start_rank < next_rank || tier.end_rank < tier.start_rank || tier.bps == 0 {
+                return false;
+            }
+            next_rank = tier.end_rank.saturating_add(1);
+            total_bps += tier.bps as u32;
+        }
+
+        !tiers.is_empty()
+            && tiers.len() <= MatchPool::MAX_PRIZE_TIERS
+            && total_bps <= MatchPool::MAX_FEE_BPS as u32
+    }
+}
+
+#[account]
+#[derive(Default)]
 pub struct UserDeposit {
     pub user: Pubkey,
+    pub match_pool: Pubkey,
+    pub amount: u64,
+    pub entries: u32,
+    pub team_count: u32,
+    pub bump: u8,
+}
+
+impl UserDeposit {
+    pub const INIT_SPACE: usize =
+        32 +                          // user: Pubkey
+        32 +                          // match_pool: Pubkey
+        8 +                           // amount: u64
+        4 +                           // entries: u32
+        4 +                           // team_count: u32
+        1;                            // bump: u8
+
+    /// What `entries` of this deposit's entries are worth. Never more than the
+    /// entry fee each, and never more than the share of what actually arrived.
+    pub fn stake_for(&self, entries: u32, entry_fee: u64) -> u64 {
+        if self.entries == 0 {
+            return 0;
+        }
+        let paid_share = (self.amount as u128 * entries as u128 / self.entries as u128) as u64;
+        paid_share.min(entry_fee.saturating_mul(entries as u64))
+    }
+
+    /// Entries not backing a committed team
+    pub fn unused_entries(&self) -> u32 {
+        self.entries.saturating_sub(self.team_count)
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct TeamEntry {
+    pub user: Pubkey,
+    pub match_pool: Pubkey,
+    pub team_index: u16,
+    pub commitment: [u8; 32],
+    pub is_revealed: bool,
+    pub lineup: TeamLineup,
+    pub score: i64,
+    pub is_scored: bool,
+    pub entry_fee: u64,
+    pub prize_amount: u64,
+    pub is_claimed: bool,
+    pub bump: u8,
+}
+
+impl TeamEntry {
+    pub const INIT_SPACE: usize =
+        32 +                          // user: Pubkey
+        32 +                          // match_pool: Pubkey
+        2 +                           // team_index: u16
+        32 +                          // commitment: [u8; 32]
+        1 +                           // is_revealed: bool
+        TeamLineup::INIT_SPACE +      // lineup: TeamLineup
+        8 +                           // score: i64
+        1 +                           // is_scored: bool
+        8 +                           // entry_fee: u64
+        8 +                           // prize_amount: u64
+        1 +                           // is_claimed: bool
+        1;                            // bump: u8
+}
+
+/// A fantasy team, kept hidden behind a salted commitment until registration closes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct TeamLineup {
+    pub captain: u32,
+    pub vice_captain: u32,
+    pub players: [u32; 11],
+}
+
+impl TeamLineup {
+    pub const INIT_SPACE: usize =
+        4 +                           // captain: u32
+        4 +                           // vice_captain: u32
+        4 * 11;                       // players: [u32; 11]
+
+    /// Bound to the team's owner and slot, so a published commitment cannot be
+    /// copied into another entry and revealed with someone else's lineup.
+    pub fn commitment(
+        &self,
+        user: &Pubkey,
+        match_pool: &Pubkey,
+        team_index: u16,
+        salt: &[u8; 32],
+    ) -> Result<[u8; 32]> {
+        Ok(hashv(&[
+            user.as_ref(),
+            match_pool.as_ref(),
+            &team_index.to_le_bytes(),
+            &self.try_to_vec()?,
+            salt,
+        ]).to_bytes())
+    }
+
+    /// Captain and vice-captain must be two different players of the eleven
+    pub fn is_valid(&self) -> bool {
+        let unique_players = self.players.iter().enumerate()
+            .all(|(i, player)| !self.players[..i].contains(player));
+
+        unique_players
+            && self.captain != self.vice_captain
+            && self.players.contains(&self.captain)
+            && self.players.contains(&self.vice_captain)
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct ClaimReceipt {
+    pub user: Pubkey,
+    pub match_pool: Pubkey,
     pub amount: u64,
-}
\ No newline at end of file
+    pub bump: u8,
+}
+
+impl ClaimReceipt {
+    pub const INIT_SPACE: usize =
+        32 +                          // user: Pubkey
+        32 +                          // match_pool: Pubkey
+        8 +                           // amount: u64
+        1;                            // bump: u8
+}
+/// Fantasy points per player, posted by the config oracle
+#[account]
+#[derive(Default)]
+pub struct MatchScores {
+    pub match_pool: Pubkey,
+    pub points: Vec<PlayerPoints>,
+    pub is_final: bool,
+    pub bump: u8,
+}
+
+impl MatchScores {
+    pub const MAX_PLAYERS: usize = 50;
+
+    pub const INIT_SPACE: usize =
+        32 +                          // match_pool: Pubkey
+        4 + (Self::MAX_PLAYERS * PlayerPoints::INIT_SPACE) + // points: Vec<PlayerPoints> (max 50 players)
+        1 +                           // is_final: bool
+        1;                            // bump: u8
+
+    /// Players without an entry have not scored
+    pub fn points_for(&self, player_id: u32) -> i64 {
+        self.points.iter()
+            .find(|p| p.player_id == player_id)
+            .map_or(0, |p| p.points as i64)
+    }
+
+    /// Scores a lineup in half points so the vice-captain's 1.5x needs no rounding:
+    /// players count twice, the vice-captain three times and the captain four times
+    pub fn team_score(&self, lineup: &TeamLineup) -> i64 {
+        lineup.players.iter()
+            .map(|&player| {
+                let multiplier = if player == lineup.captain {
+                    4
+                } else if player == lineup.vice_captain {
+                    3
+                } else {
+                    2
+                };
+                self.points_for(player) * multiplier
+            })
+            .sum()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct PlayerPoints {
+    pub player_id: u32,
+    pub points: i32,
+}
+
+impl PlayerPoints {
+    pub const INIT_SPACE: usize =
+        4 +                           // player_id: u32
+        4;                            // points: i32
+}
+
+#[account]
+#[derive(Default)]
+pub struct Dispute {
+    pub disputer: Pubkey,
+    pub match_pool: Pubkey,
+    pub bond: u64,
+    pub reason: [u8; 32],
+    pub bump: u8,
+}
+
+impl Dispute {
+    pub const INIT_SPACE: usize =
+        32 +                          // disputer: Pubkey
+        32 +                          // match_pool: Pubkey
+        8 +                           // bond: u64
+        32 +                          // reason: [u8; 32]
+        1;                            // bump: u8
+}
+
+/// A mint contests may be created in, with the entry fees accepted for it
+#[account]
+pub struct AllowedMint {
+    pub mint: Pubkey,
+    pub min_entry_fee: u64,
+    pub max_entry_fee: u64,
+    pub bump: u8,
+}
+
+impl AllowedMint {
+    pub const INIT_SPACE: usize =
+        32 +                          // mint: Pubkey
+        8 +                           // min_entry_fee: u64
+        8 +                           // max_entry_fee: u64
+        1;                            // bump: u8
+
+    pub fn accepts_entry_fee(&self, entry_fee: u64) -> bool {
+        (self.min_entry_fee..=self.max_entry_fee).contains(&entry_fee)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tier(start_rank: u16, end_rank: u16, bps: u16) -> PrizeTier {
+        PrizeTier { start_rank, end_rank, bps }
+    }
+
+    /// A pool with `prize_pool` left for prizes after a 5% fee
+    fn pool_with_table(prize_pool: u64, prize_table: Vec<PrizeTier>) -> MatchPool {
+        let total_deposited = prize_pool * 100 / 95;
+        MatchPool {
+            total_deposited,
+            fee_collected: total_deposited - prize_pool,
+            prize_table,
+            ..Default::default()
+        }
+    }
+
+    fn tiered_pool() -> MatchPool {
+        pool_with_table(1_000_000, vec![tier(1, 1, 5_000), tier(2, 3, 3_000), tier(4, 5, 2_000)])
+    }
+
+    #[test]
+    fn prize_amounts_follow_the_table() {
+        let pool = tiered_pool();
+
+        assert_eq!(
+            pool.prize_amounts(0, &[1, 2, 3, 4, 5, 6]),
+            vec![500_000, 150_000, 150_000, 100_000, 100_000, 0]
+        );
+    }
+
+    #[test]
+    fn prize_amounts_for_a_later_slice() {
+        let pool = tiered_pool();
+
+        assert_eq!(pool.prize_amounts(3, &[4, 5]), vec![100_000, 100_000]);
+        assert_eq!(pool.prize_amounts(5, &[6, 7]), vec![0, 0]);
+    }
+
+    #[test]
+    fn prize_amounts_split_ties_across_the_places_they_cover() {
+        let pool = tiered_pool();
+
+        assert_eq!(pool.prize_amounts(0, &[1, 1, 3]), vec![325_000, 325_000, 150_000]);
+        assert_eq!(pool.prize_amounts(0, &[1, 2, 2, 2, 5]), vec![500_000, 133_333, 133_333, 133_333, 100_000]);
+    }
+
+    #[test]
+    fn prize_amounts_round_down_against_the_table_pot() {
+        let pool = pool_with_table(100, vec![tier(1, 3, 10_000)]);
+        let amounts = pool.prize_amounts(0, &[1, 2, 3]);
+
+        assert_eq!(amounts, vec![33, 33, 33]);
+        assert_eq!(pool.table_pot(3) - amounts.iter().sum::<u64>(), 1);
+    }
+
+    #[test]
+    fn prize_amounts_pay_the_sponsored_guarantee() {
+        let mut pool = pool_with_table(95_000, vec![tier(1, 1, 10_000)]);
+        pool.guaranteed_amount = 200_000;
+        pool.sponsored_amount = 150_000;
+
+        assert_eq!(pool.prize_amounts(0, &[1]), vec![200_000]);
+    }
+
+    #[test]
+    fn sponsors_only_pay_for_prizes_winners_receive() {
+        // A 1000 guarantee over 100 of entries, the table pays out 60%
+        let mut pool = pool_with_table(100, vec![tier(1, 1, 6_000)]);
+        pool.guaranteed_amount = 1_000;
+        pool.sponsored_amount = 900;
+        pool.winner_count = 1;
+        pool.distributed_so_far = 600;
+
+        assert_eq!(pool.unused_sponsorship(), 400);
+
+        pool.status = MatchStatus::Cancelled;
+        assert_eq!(pool.unused_sponsorship(), 900);
+    }
+
+    #[test]
+    fn rolled_over_dust_adds_to_the_prizes() {
+        let mut pool = pool_with_table(95_000, vec![tier(1, 1, 10_000)]);
+        pool.rollover_amount = 5_000;
+
+        assert_eq!(pool.prize_amounts(0, &[1]), vec![100_000]);
+    }
+
+    #[test]
+    fn valid_rankings() {
+        assert!(MatchPool::is_valid_ranking(0, &[1, 2, 3]));
+        assert!(MatchPool::is_valid_ranking(0, &[1, 1, 3]));
+        assert!(MatchPool::is_valid_ranking(0, &[1, 2, 2, 2, 5]));
+        assert!(MatchPool::is_valid_ranking(3, &[4, 4, 6]));
+    }
+
+    #[test]
+    fn invalid_rankings() {
+        // Must start at the slice's first place
+        assert!(!MatchPool::is_valid_ranking(0, &[2, 3]));
+        // Gaps only follow ties
+        assert!(!MatchPool::is_valid_ranking(0, &[1, 3]));
+        // A tie skips the places it covers
+        assert!(!MatchPool::is_valid_ranking(0, &[1, 1, 2]));
+        // A slice cannot start in the middle of a tie
+        assert!(!MatchPool::is_valid_ranking(1, &[1, 3]));
+        // Out of order
+        assert!(!MatchPool::is_valid_ranking(0, &[1, 3, 2]));
+    }
+
+    #[test]
+    fn results_can_be_voided_until_payouts_finish() {
+        let mut pool = MatchPool { results_hash: [1u8; 32], winner_count: 3, ..Default::default() };
+        assert!(pool.can_void_results());
+
+        pool.distribution_cursor = 2;
+        assert!(pool.can_void_results());
+        pool.void_results();
+        assert!(!pool.can_void_results());
+        // A partly paid distribution still owns the settlement
+        assert!(pool.distribution_started());
+
+        let finished = MatchPool { results_hash: [1u8; 32], winner_count: 3, distribution_cursor: 3, ..Default::default() };
+        assert!(!finished.can_void_results());
+
+        let claimed = MatchPool { results_hash: [1u8; 32], total_allocated: 100, total_claimed: 50, ..Default::default() };
+        assert!(!claimed.can_void_results());
+    }
+
+    #[test]
+    fn valid_prize_tables() {
+        assert!(PrizeTier::is_valid_table(&[tier(1, 1, 10_000)]));
+        assert!(PrizeTier::is_valid_table(&[tier(1, 1, 5_000), tier(2, 3, 3_000), tier(4, 10, 2_000)]));
+        // Less than the full pool is allowed, the rest goes to the treasury
+        assert!(PrizeTier::is_valid_table(&[tier(1, 2, 6_000)]));
+    }
+
+    #[test]
+    fn invalid_prize_tables() {
+        assert!(!PrizeTier::is_valid_table(&[]));
+        // Ranks start at 1
+        assert!(!PrizeTier::is_valid_table(&[tier(0, 1, 10_000)]));
+        // Overlapping and out of order tiers
+        assert!(!PrizeTier::is_valid_table(&[tier(1, 2, 5_000), tier(2, 3, 5_000)]));
+        assert!(!PrizeTier::is_valid_table(&[tier(2, 3, 5_000), tier(1, 1, 5_000)]));
+        // Empty rank range and zero share
+        assert!(!PrizeTier::is_valid_table(&[tier(2, 1, 10_000)]));
+        assert!(!PrizeTier::is_valid_table(&[tier(1, 1, 0)]));
+        // More than 100%
+        assert!(!PrizeTier::is_valid_table(&[tier(1, 1, 6_000), tier(2, 2, 5_000)]));
+        // Too many tiers
+        let tiers: Vec<PrizeTier> = (1..=11).map(|rank| tier(rank, rank, 100)).collect();
+        assert!(!PrizeTier::is_valid_table(&tiers));
+    }
+
+    #[test]
+    fn team_score_weights_the_captain_and_vice_captain() {
+        let scores = MatchScores {
+            points: (1..=10).map(|player_id| PlayerPoints { player_id, points: 10 }).collect(),
+            ..Default::default()
+        };
+        let lineup = TeamLineup { captain: 1, vice_captain: 2, players: [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11] };
+
+        // In half points: captain 10 x4, vice-captain 10 x3, eight players 10 x2 and
+        // player 11, who has no points yet
+        assert_eq!(scores.team_score(&lineup), 230);
+    }
+}
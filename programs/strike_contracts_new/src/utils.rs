@@ -0,0 +1,173 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+    TransferChecked,
+};
+
+use std::collections::HashMap;
+
+use crate::{error::CustomError, state::{DustSink, MatchPool, TeamEntry, UserDeposit}};
+
+/// Transfers `amount` out of the pool token account, signed by the match pool PDA
+pub fn transfer_from_pool<'info>(
+    match_pool: &Account<'info, MatchPool>,
+    pool_token_account: &InterfaceAccount<'info, TokenAccount>,
+    to: AccountInfo<'info>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    let seeds = &[
+        b"match_pool",
+        match_pool.match_id.as_bytes(),
+        &[match_pool.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let transfer_cpi_accounts = TransferChecked {
+        from: pool_token_account.to_account_info(),
+        mint: token_mint.to_account_info(),
+        to,
+        authority: match_pool.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        transfer_cpi_accounts,
+        signer,
+    );
+
+    transfer_checked(cpi_ctx, amount, token_mint.decimals)
+}
+
+/// Picks the token account `amount` of dust is swept to. A rollover is recorded
+/// on the receiving pool so it adds to that pool's prizes, unless that pool has
+/// already started settling, in which case the dust goes to the treasury.
+pub fn dust_destination<'a, 'info>(
+    match_pool: &MatchPool,
+    rollover_pool: Option<&mut Account<'info, MatchPool>>,
+    sweep_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    treasury_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    amount: u64,
+) -> Result<&'a InterfaceAccount<'info, TokenAccount>> {
+    let DustSink::Rollover(rollover_key) = match_pool.dust_sink else {
+        return Ok(sweep_token_account);
+    };
+
+    let rollover_pool = rollover_pool.ok_or(error!(CustomError::InvalidDustAccount))?;
+    require!(rollover_pool.key() == rollover_key, CustomError::InvalidDustAccount);
+
+    if !rollover_pool.accepts_rollover(Clock::get()?.unix_timestamp) {
+        return Ok(treasury_token_account);
+    }
+
+    require!(
+        sweep_token_account.key() == rollover_pool.pool_token_address()?,
+        CustomError::InvalidDustAccount
+    );
+    rollover_pool.rollover_amount = rollover_pool.rollover_amount.checked_add(amount)
+        .ok_or(error!(CustomError::ArithmeticOverflow))?;
+
+    Ok(sweep_token_account)
+}
+
+/// Closes a winner's wrapped SOL account so the prize arrives as native lamports.
+/// A no-op for SPL contests.
+pub fn unwrap_if_native<'info>(
+    match_pool: &MatchPool,
+    token_account: &InterfaceAccount<'info, TokenAccount>,
+    owner: AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    if !match_pool.is_native() {
+        return Ok(());
+    }
+
+    let close_cpi_accounts = CloseAccount {
+        account: token_account.to_account_info(),
+        destination: owner.clone(),
+        authority: owner,
+    };
+
+    close_account(CpiContext::new(token_program.to_account_info(), close_cpi_accounts))
+}
+
+/// Checks a purchase of `entries` against the pool's limits and returns its price
+pub fn entry_cost(match_pool: &MatchPool, user_deposit: &UserDeposit, entries: u32) -> Result<u64> {
+    require!(entries > 0, CustomError::InvalidEntryCount);
+
+    let user_entries = user_deposit.entries.checked_add(entries)
+        .ok_or(error!(CustomError::ArithmeticOverflow))?;
+    require!(user_entries <= match_pool.max_entries_per_user, CustomError::EntryLimitExceeded);
+
+    if user_deposit.entries == 0 {
+        require!(match_pool.participant_count < match_pool.max_participants, CustomError::ContestFull);
+    }
+
+    match_pool.entry_fee.checked_mul(entries as u64)
+        .ok_or(error!(CustomError::ArithmeticOverflow))
+}
+
+/// Credits `received` tokens for `entries` new entries, filling the deposit in on first use
+pub fn record_entries(
+    match_pool: &mut Account<MatchPool>,
+    user_deposit: &mut Account<UserDeposit>,
+    user: Pubkey,
+    bump: u8,
+    entries: u32,
+    received: u64,
+) -> Result<()> {
+    let is_new_participant = user_deposit.entries == 0;
+
+    if user_deposit.user == Pubkey::default() {
+        user_deposit.user = user;
+        user_deposit.match_pool = match_pool.key();
+        user_deposit.bump = bump;
+    }
+
+    user_deposit.amount += received;
+    user_deposit.entries += entries;
+
+    match_pool.total_deposited += received;
+    if is_new_participant {
+        match_pool.participant_count += 1;
+    }
+
+    Ok(())
+}
+
+/// Maps the pool's team entries found in `remaining_accounts` by `(user, team_index)`
+pub fn collect_team_entries<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+    match_pool: &Pubkey,
+    program_id: &Pubkey,
+) -> HashMap<(Pubkey, u16), &'a AccountInfo<'info>> {
+    let mut team_entries = HashMap::new();
+
+    for account in accounts.iter() {
+        if account.owner != program_id {
+            continue;
+        }
+        let data = account.data.borrow();
+        if let Ok(team_entry) = TeamEntry::try_deserialize(&mut &data[..]) {
+            if team_entry.match_pool == *match_pool {
+                team_entries.insert((team_entry.user, team_entry.team_index), account);
+            }
+        }
+    }
+
+    team_entries
+}
+
+/// Loads a team entry passed outside the `Accounts` struct, applies `update` and writes it back
+pub fn update_team_entry(
+    account: &AccountInfo,
+    update: impl FnOnce(&mut TeamEntry) -> Result<()>,
+) -> Result<()> {
+    require!(account.is_writable, CustomError::TeamEntryNotFound);
+
+    let mut data = account.try_borrow_mut_data()?;
+    let mut team_entry = TeamEntry::try_deserialize(&mut &data[..])?;
+    update(&mut team_entry)?;
+    team_entry.try_serialize(&mut &mut data[..])
+}
\ No newline at end of file
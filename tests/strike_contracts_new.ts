@@ -1,16 +1,121 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { StrikeContractsNew } from "../target/types/strike_contracts_new";
-import { PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } from "@solana/web3.js";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  Transaction,
+} from "@solana/web3.js";
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
+  createAccount,
   createAssociatedTokenAccount,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
   createMint,
+  ExtensionType,
+  getMintLen,
+  getOrCreateAssociatedTokenAccount,
   mintTo,
+  NATIVE_MINT,
+  TOKEN_2022_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import { BN } from "bn.js";
 import { expect } from "chai";
+import { createHash, randomBytes } from "crypto";
+
+// Matches `TeamLineup::commitment`: sha256 over the owner, the match pool and
+// the little-endian team index, then the borsh lineup (13 little-endian u32s)
+// and the 32-byte salt
+function commitLineup(
+  user: PublicKey,
+  matchPool: PublicKey,
+  teamIndex: number,
+  captain: number,
+  viceCaptain: number,
+  players: number[],
+  salt: Buffer
+) {
+  const index = Buffer.alloc(2);
+  index.writeUInt16LE(teamIndex);
+  const data = Buffer.alloc(4 * 13);
+  [captain, viceCaptain, ...players].forEach((id, i) =>
+    data.writeUInt32LE(id, i * 4)
+  );
+  return Array.from(
+    createHash("sha256")
+      .update(user.toBuffer())
+      .update(matchPool.toBuffer())
+      .update(index)
+      .update(data)
+      .update(salt)
+      .digest()
+  );
+}
+
+const sha256 = (...parts: Buffer[]) =>
+  parts.reduce((hash, part) => hash.update(part), createHash("sha256")).digest();
+
+// Matches `merkle::MerkleTree`: sorted sibling pairs, with an odd node carried
+// up unchanged
+function merkleTree(leaves: Buffer[]) {
+  const levels = [leaves];
+  while (levels[levels.length - 1].length > 1) {
+    const level = levels[levels.length - 1];
+    const next: Buffer[] = [];
+    for (let i = 0; i < level.length; i += 2) {
+      if (i + 1 === level.length) {
+        next.push(level[i]);
+        continue;
+      }
+      const [left, right] = [level[i], level[i + 1]].sort(Buffer.compare);
+      next.push(sha256(Buffer.from([1]), left, right));
+    }
+    levels.push(next);
+  }
+
+  const proof = (index: number) =>
+    levels.slice(0, -1).flatMap((level, depth) => {
+      const sibling = level[(index >> depth) ^ 1];
+      return sibling ? [Array.from(sibling)] : [];
+    });
+
+  return { root: Array.from(levels[levels.length - 1][0]), proof };
+}
+
+// Leaves match `merkle::winner_leaf_hash`
+function winnersTree(
+  winners: { user: PublicKey; teamIndex: number; rank: number }[]
+) {
+  return merkleTree(
+    winners.map((winner, position) => {
+      const fields = Buffer.alloc(8);
+      fields.writeUInt32LE(position, 0);
+      fields.writeUInt16LE(winner.teamIndex, 4);
+      fields.writeUInt16LE(winner.rank, 6);
+      return sha256(
+        Buffer.from([2]),
+        fields.subarray(0, 4),
+        winner.user.toBuffer(),
+        fields.subarray(4)
+      );
+    })
+  );
+}
+
+// Leaves match `merkle::leaf_hash`
+function prizeTree(prizes: { user: PublicKey; amount: number }[]) {
+  return merkleTree(
+    prizes.map(({ user, amount }) => {
+      const encodedAmount = Buffer.alloc(8);
+      encodedAmount.writeBigUInt64LE(BigInt(amount));
+      return sha256(Buffer.from([0]), user.toBuffer(), encodedAmount);
+    })
+  );
+}
 
 describe("fantasy_cricket_magicblock", () => {
   const provider = anchor.AnchorProvider.env();
@@ -49,15 +154,36 @@ describe("fantasy_cricket_magicblock", () => {
 
   const admin = provider.wallet.payer;
 
+  let config: PublicKey;
   let usdcMint: PublicKey;
+  let allowedMint: PublicKey;
   let adminTokenAccount: PublicKey;
   let matchPool: PublicKey;
   let poolTokenAccount: PublicKey;
+  let userDeposit: PublicKey;
+  let teamEntry: PublicKey;
+  const lineup = {
+    captain: 1,
+    viceCaptain: 2,
+    players: [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+  };
+  const salt = randomBytes(32);
 
   const uniqueId = new Date().getTime().toString().slice(-6);
   const matchId = `MATCH123_${uniqueId}`;
   const currentTimestamp = Math.floor(Date.now() / 1000);
-  const registrationEndTime = currentTimestamp + 3600;
+  // Short window so the team can be revealed once registration closes
+  const registrationEndTime = currentTimestamp + 60;
+  const matchStartTime = registrationEndTime + 60;
+  const feeBps = 500; // 5% platform fee
+  // Seconds results can be disputed, kept short so payouts unlock quickly
+  const challengePeriod = 2;
+  const entryRules = {
+    entryFee: new BN(10_000_000), // 10 USDC per entry
+    maxEntriesPerUser: 3,
+    maxParticipants: 100,
+    minParticipants: 1,
+  };
 
   const program = anchor.workspace
     .StrikeContractsNew as Program<StrikeContractsNew>;
@@ -96,6 +222,55 @@ describe("fantasy_cricket_magicblock", () => {
     );
 
     // Find PDAs
+    [config] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      program.programId
+    );
+
+    // Only the upgrade authority may create the config
+    const [programData] = PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+
+    // The config is a singleton, only create it on a fresh cluster
+    if ((await provider.connection.getAccountInfo(config)) === null) {
+      const txHash = await program.methods
+        .initializeConfig(provider.wallet.publicKey)
+        .accounts({
+          config,
+          program: program.programId,
+          programData,
+          superAdmin: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      console.log(`Initialize config txHash: ${txHash}`);
+    }
+
+    await program.methods
+      .setChallengePeriod(new BN(challengePeriod))
+      .accounts({ config, superAdmin: provider.wallet.publicKey })
+      .rpc();
+
+    [allowedMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("allowed_mint"), usdcMint.toBuffer()],
+      program.programId
+    );
+
+    // Contests can only be created in allowlisted mints
+    const allowMintTx = await program.methods
+      .setAllowedMint(new BN(1_000_000), new BN(100_000_000))
+      .accounts({
+        config,
+        allowedMint,
+        tokenMint: usdcMint,
+        superAdmin: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    console.log(`Allow mint txHash: ${allowMintTx}`);
+
     [matchPool] = PublicKey.findProgramAddressSync(
       [Buffer.from("match_pool"), Buffer.from(matchId)],
       program.programId
@@ -106,6 +281,27 @@ describe("fantasy_cricket_magicblock", () => {
       program.programId
     );
 
+    [userDeposit] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("user_deposit"),
+        Buffer.from(matchId),
+        provider.wallet.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+
+    const teamIndex = Buffer.alloc(2);
+    teamIndex.writeUInt16LE(0);
+    [teamEntry] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("team_entry"),
+        Buffer.from(matchId),
+        provider.wallet.publicKey.toBuffer(),
+        teamIndex,
+      ],
+      program.programId
+    );
+
     console.log("Match pool address:", matchPool.toString());
     console.log("Pool token account address:", poolTokenAccount.toString());
   });
@@ -116,11 +312,20 @@ describe("fantasy_cricket_magicblock", () => {
 
     try {
       let tx = await program.methods
-        .initialize(matchId, new BN(registrationEndTime))
+        .initialize(
+          matchId,
+          new BN(registrationEndTime),
+          new BN(matchStartTime),
+          feeBps,
+          entryRules
+        )
         .accounts({
           matchPool,
+          config,
           poolTokenAccount,
           tokenMint: usdcMint,
+          allowedMint,
+          treasuryTokenAccount: adminTokenAccount,
           admin: provider.wallet.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -152,14 +357,16 @@ describe("fantasy_cricket_magicblock", () => {
       );
       expect(matchPoolAccount.matchId).to.equal(matchId);
       expect(matchPoolAccount.totalDeposited.toNumber()).to.equal(0);
-      expect(matchPoolAccount.isActive).to.be.true;
+      expect(matchPoolAccount.status).to.deep.equal({ open: {} });
+      expect(matchPoolAccount.feeBps).to.equal(feeBps);
+      expect(matchPoolAccount.entryFee.toNumber()).to.equal(10_000_000);
     } catch (error) {
       console.error("Error initializing match pool:", error);
       throw error;
     }
   });
 
-  it("Allows admin to deposit USDC", async () => {
+  it("Allows admin to enter the contest", async () => {
     console.log(
       "Admin token balance:",
       await provider.connection
@@ -169,15 +376,17 @@ describe("fantasy_cricket_magicblock", () => {
 
     const depositAmount = 10_000_000; // 10 USDC
 
-    console.log("Admin depositing...");
+    console.log("Admin entering contest...");
     const start = Date.now();
 
     try {
       let tx = await program.methods
-        .deposit(new BN(depositAmount))
+        .enterContest(1)
         .accounts({
           matchPool,
           poolTokenAccount,
+          tokenMint: usdcMint,
+          userDeposit,
           userTokenAccount: adminTokenAccount,
           user: provider.wallet.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -210,12 +419,61 @@ describe("fantasy_cricket_magicblock", () => {
       expect(matchPoolAccount.totalDeposited.toNumber()).to.equal(
         depositAmount
       );
+
+      const userDepositAccount = await program.account.userDeposit.fetch(
+        userDeposit
+      );
+      expect(userDepositAccount.user.toString()).to.equal(
+        provider.wallet.publicKey.toString()
+      );
+      expect(userDepositAccount.amount.toNumber()).to.equal(depositAmount);
+      expect(userDepositAccount.entries).to.equal(1);
     } catch (error) {
       console.error("Error verifying deposit:", error);
     }
   });
 
-  it("Simulates multiple deposits to get enough tokens for distribution", async () => {
+  it("Rejects entries while the pool is paused", async () => {
+    await program.methods
+      .setPoolPaused(true)
+      .accounts({
+        matchPool,
+        config,
+        guardian: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    try {
+      await program.methods
+        .enterContest(1)
+        .accounts({
+          matchPool,
+          config,
+          poolTokenAccount,
+          tokenMint: usdcMint,
+          userDeposit,
+          userTokenAccount: adminTokenAccount,
+          user: provider.wallet.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      expect.fail("Entry should have been rejected while paused");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("ProgramPaused");
+    } finally {
+      await program.methods
+        .setPoolPaused(false)
+        .accounts({
+          matchPool,
+          config,
+          guardian: provider.wallet.publicKey,
+        })
+        .rpc();
+    }
+  });
+
+  it("Simulates multiple entries to get enough tokens for distribution", async () => {
     console.log("Making additional deposits...");
 
     const depositAmount = 10_000_000; // 10 USDC
@@ -223,10 +481,12 @@ describe("fantasy_cricket_magicblock", () => {
     try {
       console.log("Making second deposit...");
       const txHash = await program.methods
-        .deposit(new BN(depositAmount))
+        .enterContest(1)
         .accounts({
           matchPool,
           poolTokenAccount,
+          tokenMint: usdcMint,
+          userDeposit,
           userTokenAccount: adminTokenAccount,
           user: provider.wallet.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -242,10 +502,12 @@ describe("fantasy_cricket_magicblock", () => {
     try {
       console.log("Making third deposit...");
       const txHash = await program.methods
-        .deposit(new BN(depositAmount))
+        .enterContest(1)
         .accounts({
           matchPool,
           poolTokenAccount,
+          tokenMint: usdcMint,
+          userDeposit,
           userTokenAccount: adminTokenAccount,
           user: provider.wallet.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -278,6 +540,78 @@ describe("fantasy_cricket_magicblock", () => {
     }
   });
 
+  it("Rejects entries beyond the per-user limit", async () => {
+    try {
+      await program.methods
+        .enterContest(1)
+        .accounts({
+          matchPool,
+          poolTokenAccount,
+          tokenMint: usdcMint,
+          userDeposit,
+          userTokenAccount: adminTokenAccount,
+          user: provider.wallet.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      expect.fail("Fourth entry should have been rejected");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("EntryLimitExceeded");
+    }
+  });
+
+  it("Commits a team against a paid entry", async () => {
+    const commitment = commitLineup(
+      provider.wallet.publicKey,
+      matchPool,
+      0,
+      lineup.captain,
+      lineup.viceCaptain,
+      lineup.players,
+      salt
+    );
+
+    const txHash = await program.methods
+      .commitTeam(0, commitment)
+      .accounts({
+        matchPool,
+        userDeposit,
+        teamEntry,
+        user: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    console.log(`Commit team txHash: ${txHash}`);
+
+    const teamEntryAccount = await program.account.teamEntry.fetch(teamEntry);
+    expect(teamEntryAccount.teamIndex).to.equal(0);
+    expect(teamEntryAccount.commitment).to.deep.equal(commitment);
+    expect(teamEntryAccount.isRevealed).to.be.false;
+    expect(teamEntryAccount.entryFee.toNumber()).to.equal(10_000_000);
+  });
+
+  it("Reveals the team after registration closes", async () => {
+    const waitMs = (registrationEndTime + 2) * 1000 - Date.now();
+    if (waitMs > 0) {
+      await new Promise((resolve) => setTimeout(resolve, waitMs));
+    }
+
+    const txHash = await program.methods
+      .revealTeam(0, lineup, Array.from(salt))
+      .accounts({
+        matchPool,
+        teamEntry,
+        user: provider.wallet.publicKey,
+      })
+      .rpc();
+    console.log(`Reveal team txHash: ${txHash}`);
+
+    const teamEntryAccount = await program.account.teamEntry.fetch(teamEntry);
+    expect(teamEntryAccount.isRevealed).to.be.true;
+    expect(teamEntryAccount.lineup.captain).to.equal(lineup.captain);
+  });
+
   it("Allows admin to end the match", async () => {
     console.log("Ending match...");
 
@@ -286,7 +620,12 @@ describe("fantasy_cricket_magicblock", () => {
         .endMatch()
         .accounts({
           matchPool,
+          config,
+          poolTokenAccount,
+          tokenMint: usdcMint,
+          treasuryTokenAccount: adminTokenAccount,
           admin: provider.wallet.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
         .rpc();
@@ -299,16 +638,110 @@ describe("fantasy_cricket_magicblock", () => {
 
     // Verify match is ended
     const matchPoolAccount = await program.account.matchPool.fetch(matchPool);
-    expect(matchPoolAccount.isActive).to.be.false;
+    expect(matchPoolAccount.status).to.deep.equal({ scoring: {} });
+    expect(matchPoolAccount.feeCollected.toNumber()).to.equal(1_500_000);
+  });
+
+  it("Sets the prize table", async () => {
+    // Winner takes all, dust goes to the treasury
+    const prizeTable = [{ startRank: 1, endRank: 1, bps: 10_000 }];
+
+    const txHash = await program.methods
+      .setPrizeTable(prizeTable, { treasury: {} })
+      .accounts({
+        matchPool,
+        config,
+        admin: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    console.log(`Set prize table txHash: ${txHash}`);
+
+    const matchPoolAccount = await program.account.matchPool.fetch(matchPool);
+    expect(matchPoolAccount.prizeTable.length).to.equal(1);
+  });
+
+  it("Posts results and opens the challenge window", async () => {
+    const resultsHash = winnersTree([
+      { user: provider.wallet.publicKey, teamIndex: 0, rank: 1 },
+    ]).root;
+
+    // Without winners or allocations the results could never be settled
+    try {
+      await program.methods
+        .postResults(resultsHash, 0)
+        .accounts({
+          matchPool,
+          config,
+          admin: provider.wallet.publicKey,
+        })
+        .rpc();
+      expect.fail("Results without winners should have been rejected");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("NoPrizeAllocations");
+    }
+
+    const txHash = await program.methods
+      .postResults(resultsHash, 1)
+      .accounts({
+        matchPool,
+        config,
+        admin: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    console.log(`Post results txHash: ${txHash}`);
+
+    const matchPoolAccount = await program.account.matchPool.fetch(matchPool);
+    expect(matchPoolAccount.resultsHash).to.deep.equal(resultsHash);
+    expect(matchPoolAccount.winnerCount).to.equal(1);
+
+    // Payouts unlock once the challenge window closes
+    const waitMs =
+      (matchPoolAccount.challengeEndTime.toNumber() + 1) * 1000 - Date.now();
+    if (waitMs > 0) {
+      await new Promise((resolve) => setTimeout(resolve, waitMs));
+    }
+  });
+
+  it("Rejects duplicate winners", async () => {
+    const winner = { user: provider.wallet.publicKey, teamIndex: 0, rank: 1 };
+
+    try {
+      await program.methods
+        .distributePrizes(0, [winner, { ...winner, rank: 2 }], [[], []])
+        .accounts({
+          matchPool,
+          config,
+          poolTokenAccount,
+          tokenMint: usdcMint,
+          sweepTokenAccount: adminTokenAccount,
+          treasuryTokenAccount: adminTokenAccount,
+          rolloverPool: null,
+          admin: provider.wallet.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts([
+          { pubkey: adminTokenAccount, isWritable: true, isSigner: false },
+          { pubkey: teamEntry, isWritable: true, isSigner: false },
+        ])
+        .rpc();
+      expect.fail("Duplicate winner should have been rejected");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("DuplicateWinner");
+    }
   });
 
   it("Distributes prizes", async () => {
     console.log("Distributing prizes to participants...");
 
-    const prizeDistributions = [
+    // Ordered best first, amounts come from the prize table
+    const winners = [
       {
         user: provider.wallet.publicKey,
-        amount: new BN(30_000_000), // 30 USDC (All deposits)
+        teamIndex: 0,
+        rank: 1,
       },
     ];
 
@@ -318,14 +751,28 @@ describe("fantasy_cricket_magicblock", () => {
         isWritable: true,
         isSigner: false,
       },
+      {
+        pubkey: teamEntry,
+        isWritable: true,
+        isSigner: false,
+      },
     ];
 
     try {
       const txHash = await program.methods
-        .distributePrizes(prizeDistributions)
+        .distributePrizes(
+          0,
+          winners,
+          winners.map((_, i) => winnersTree(winners).proof(i))
+        )
         .accounts({
           matchPool,
+          config,
           poolTokenAccount,
+          tokenMint: usdcMint,
+          sweepTokenAccount: adminTokenAccount,
+          treasuryTokenAccount: adminTokenAccount,
+          rolloverPool: null,
           admin: provider.wallet.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
@@ -341,7 +788,13 @@ describe("fantasy_cricket_magicblock", () => {
 
     // Verify match is finalized
     const matchPoolAccount = await program.account.matchPool.fetch(matchPool);
-    expect(matchPoolAccount.isFinalized).to.be.true;
+    expect(matchPoolAccount.status).to.deep.equal({ settled: {} });
+    expect(matchPoolAccount.distributionCursor).to.equal(1);
+    expect(matchPoolAccount.distributedSoFar.toNumber()).to.equal(28_500_000);
+
+    const teamEntryAccount = await program.account.teamEntry.fetch(teamEntry);
+    // 28.5 USDC (All deposits after the 5% fee)
+    expect(teamEntryAccount.prizeAmount.toNumber()).to.equal(28_500_000);
 
     // Verify admin received the prizes
     const adminBalance = (
@@ -355,4 +808,1561 @@ describe("fantasy_cricket_magicblock", () => {
     );
     expect(poolBalance.value.uiAmount).to.equal(0);
   });
+
+  it("Returns the rent of the user's records once the pool is settled", async () => {
+    await program.methods
+      .closeTeamEntry(0)
+      .accounts({
+        matchPool,
+        config,
+        teamEntry,
+        user: provider.wallet.publicKey,
+      })
+      .rpc();
+    await program.methods
+      .closeUserDeposit()
+      .accounts({
+        matchPool,
+        config,
+        userDeposit,
+        user: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    expect(await program.account.teamEntry.fetchNullable(teamEntry)).to.be
+      .null;
+    expect(await program.account.userDeposit.fetchNullable(userDeposit)).to.be
+      .null;
+  });
+
+  it("Keeps the closed pool so its match id cannot be reused", async () => {
+    await program.methods
+      .closeMatchPool()
+      .accounts({
+        matchPool,
+        config,
+        poolTokenAccount,
+        tokenMint: usdcMint,
+        treasuryTokenAccount: adminTokenAccount,
+        admin: provider.wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const matchPoolAccount = await program.account.matchPool.fetch(matchPool);
+    expect(matchPoolAccount.status).to.deep.equal({ closed: {} });
+    expect(await provider.connection.getAccountInfo(poolTokenAccount)).to.be
+      .null;
+
+    try {
+      await program.methods
+        .initialize(
+          matchId,
+          new BN(Math.floor(Date.now() / 1000) + 60),
+          new BN(Math.floor(Date.now() / 1000) + 120),
+          feeBps,
+          entryRules
+        )
+        .accounts({
+          matchPool,
+          config,
+          poolTokenAccount,
+          tokenMint: usdcMint,
+          allowedMint,
+          treasuryTokenAccount: adminTokenAccount,
+          admin: provider.wallet.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .rpc();
+      expect.fail("Match id of a closed pool should not be reusable");
+    } catch (error) {
+      // `init` fails in the system program because the pool account exists
+      expect([String(error), ...(error.logs ?? [])].join("\n")).to.match(
+        /already in use/
+      );
+    }
+  });
+
+  describe("Contest flows", () => {
+    const user = provider.wallet.publicKey;
+
+    const pda = (...seeds: Buffer[]) =>
+      PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+    const balanceOf = async (tokenAccount: PublicKey) =>
+      Number(
+        (await provider.connection.getTokenAccountBalance(tokenAccount)).value
+          .amount
+      );
+
+    async function expectError(transaction: Promise<unknown>, code: string) {
+      try {
+        await transaction;
+      } catch (error) {
+        expect(error.error?.errorCode?.code, String(error)).to.equal(code);
+        return;
+      }
+      expect.fail(`Expected ${code}`);
+    }
+
+    // Each flow gets its own pool, registration closes `registrationSeconds` from now
+    async function createPool(
+      name: string,
+      registrationSeconds: number,
+      {
+        mint = usdcMint,
+        mintAllowlist = allowedMint,
+        treasuryTokenAccount = adminTokenAccount,
+        rules = entryRules,
+        tokenProgram = TOKEN_PROGRAM_ID,
+      }: {
+        mint?: PublicKey;
+        mintAllowlist?: PublicKey;
+        treasuryTokenAccount?: PublicKey;
+        rules?: typeof entryRules;
+        tokenProgram?: PublicKey;
+      } = {}
+    ) {
+      const id = `${name}_${uniqueId}`;
+      const registrationEnd =
+        Math.floor(Date.now() / 1000) + registrationSeconds;
+      const pool = {
+        id,
+        registrationEnd,
+        mint,
+        matchPool: pda(Buffer.from("match_pool"), Buffer.from(id)),
+        poolTokenAccount: pda(Buffer.from("pool_token"), Buffer.from(id)),
+        userDeposit: pda(
+          Buffer.from("user_deposit"),
+          Buffer.from(id),
+          user.toBuffer()
+        ),
+        teamEntry: pda(
+          Buffer.from("team_entry"),
+          Buffer.from(id),
+          user.toBuffer(),
+          Buffer.from([0, 0])
+        ),
+      };
+
+      await program.methods
+        .initialize(
+          id,
+          new BN(registrationEnd),
+          new BN(registrationEnd),
+          feeBps,
+          rules
+        )
+        .accounts({
+          matchPool: pool.matchPool,
+          config,
+          poolTokenAccount: pool.poolTokenAccount,
+          tokenMint: mint,
+          allowedMint: mintAllowlist,
+          treasuryTokenAccount,
+          admin: user,
+          tokenProgram,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .rpc();
+
+      return pool;
+    }
+
+    type Pool = Awaited<ReturnType<typeof createPool>>;
+
+    const enter = (pool: Pool, entries: number) =>
+      program.methods
+        .enterContest(entries)
+        .accounts({
+          matchPool: pool.matchPool,
+          config,
+          poolTokenAccount: pool.poolTokenAccount,
+          tokenMint: pool.mint,
+          userDeposit: pool.userDeposit,
+          userTokenAccount: adminTokenAccount,
+          user,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    const cancelMatch = (pool: Pool) =>
+      program.methods
+        .cancelMatch()
+        .accounts({
+          matchPool: pool.matchPool,
+          config,
+          admin: user,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    const waitUntil = async (timestamp: number) => {
+      const waitMs = timestamp * 1000 - Date.now();
+      if (waitMs > 0) {
+        await new Promise((resolve) => setTimeout(resolve, waitMs));
+      }
+    };
+
+    const teamEntryOf = (pool: Pool, teamIndex: number) =>
+      pda(
+        Buffer.from("team_entry"),
+        Buffer.from(pool.id),
+        user.toBuffer(),
+        new BN(teamIndex).toArrayLike(Buffer, "le", 2)
+      );
+
+    const commitTeam = (pool: Pool, teamIndex = 0) =>
+      program.methods
+        .commitTeam(
+          teamIndex,
+          commitLineup(
+            user,
+            pool.matchPool,
+            teamIndex,
+            lineup.captain,
+            lineup.viceCaptain,
+            lineup.players,
+            salt
+          )
+        )
+        .accounts({
+          matchPool: pool.matchPool,
+          config,
+          userDeposit: pool.userDeposit,
+          teamEntry: teamEntryOf(pool, teamIndex),
+          user,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    // Commits `teams` of the user's teams, reveals them once registration closes
+    // and ends the match
+    async function playMatch(pool: Pool, teams = 1) {
+      for (let teamIndex = 0; teamIndex < teams; teamIndex++) {
+        await commitTeam(pool, teamIndex);
+      }
+      await waitUntil(pool.registrationEnd + 2);
+
+      for (let teamIndex = 0; teamIndex < teams; teamIndex++) {
+        await program.methods
+          .revealTeam(teamIndex, lineup, Array.from(salt))
+          .accounts({
+            matchPool: pool.matchPool,
+            config,
+            teamEntry: teamEntryOf(pool, teamIndex),
+            user,
+          })
+          .rpc();
+      }
+      await program.methods
+        .endMatch()
+        .accounts({
+          matchPool: pool.matchPool,
+          config,
+          poolTokenAccount: pool.poolTokenAccount,
+          tokenMint: pool.mint,
+          treasuryTokenAccount: adminTokenAccount,
+          admin: user,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    const allocatePrize = (pool: Pool, amount: number) =>
+      program.methods
+        .setPrizeAllocations(
+          [{ user, teamIndex: 0, amount: new BN(amount) }],
+          new BN(Math.floor(Date.now() / 1000) + 3600)
+        )
+        .accounts({ matchPool: pool.matchPool, config, admin: user })
+        .remainingAccounts([
+          { pubkey: pool.teamEntry, isWritable: true, isSigner: false },
+        ])
+        .rpc();
+
+    const postResults = (pool: Pool) =>
+      program.methods
+        .postResults(Array.from(randomBytes(32)), 0)
+        .accounts({ matchPool: pool.matchPool, config, admin: user })
+        .rpc();
+
+    // Winner takes all
+    const setPrizeTable = (pool: Pool) =>
+      program.methods
+        .setPrizeTable([{ startRank: 1, endRank: 1, bps: 10_000 }], {
+          treasury: {},
+        })
+        .accounts({ matchPool: pool.matchPool, config, admin: user })
+        .rpc();
+
+    before(async () => {
+      // Enough for every flow's entries, sponsorship and bonds
+      await mintTo(
+        provider.connection,
+        admin,
+        usdcMint,
+        adminTokenAccount,
+        user,
+        200_000_000
+      );
+    });
+
+    describe("refund_deposit", () => {
+      let pool: Pool;
+
+      const refundDeposit = () =>
+        program.methods
+          .refundDeposit()
+          .accounts({
+            matchPool: pool.matchPool,
+            config,
+            poolTokenAccount: pool.poolTokenAccount,
+            tokenMint: usdcMint,
+            userDeposit: pool.userDeposit,
+            userTokenAccount: adminTokenAccount,
+            user,
+            cranker: user,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+
+      before(async () => {
+        pool = await createPool("REFUND", 60);
+        await enter(pool, 1);
+      });
+
+      it("Rejects refunds while the match is live", async () => {
+        await expectError(refundDeposit(), "InvalidMatchStatus");
+      });
+
+      it("Refunds the full deposit once the match is cancelled", async () => {
+        await cancelMatch(pool);
+        const balanceBefore = await balanceOf(adminTokenAccount);
+
+        await refundDeposit();
+
+        expect(await balanceOf(adminTokenAccount)).to.equal(
+          balanceBefore + 10_000_000
+        );
+        expect(
+          await program.account.userDeposit.fetchNullable(pool.userDeposit)
+        ).to.be.null;
+        const matchPoolAccount = await program.account.matchPool.fetch(
+          pool.matchPool
+        );
+        expect(matchPoolAccount.totalDeposited.toNumber()).to.equal(0);
+      });
+    });
+
+    describe("claim_prize", () => {
+      let pool: Pool;
+      const prize = 9_500_000; // One entry after the 5% fee
+
+      const claim = () =>
+        program.methods
+          .claimPrize(0)
+          .accounts({
+            matchPool: pool.matchPool,
+            config,
+            poolTokenAccount: pool.poolTokenAccount,
+            tokenMint: usdcMint,
+            teamEntry: pool.teamEntry,
+            winnerTokenAccount: adminTokenAccount,
+            winner: user,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+
+      before(async () => {
+        pool = await createPool("CLAIM", 15);
+        await enter(pool, 1);
+        await playMatch(pool);
+        await allocatePrize(pool, prize);
+      });
+
+      it("Rejects claims before results are posted", async () => {
+        await expectError(claim(), "PayoutsLocked");
+      });
+
+      it("Rejects claims during the challenge window", async () => {
+        await postResults(pool);
+
+        await expectError(claim(), "PayoutsLocked");
+      });
+
+      it("Pays the allocated prize once the challenge window closes", async () => {
+        const { challengeEndTime } = await program.account.matchPool.fetch(
+          pool.matchPool
+        );
+        await waitUntil(challengeEndTime.toNumber() + 1);
+        const balanceBefore = await balanceOf(adminTokenAccount);
+
+        await claim();
+
+        expect(await balanceOf(adminTokenAccount)).to.equal(
+          balanceBefore + prize
+        );
+        const teamEntryAccount = await program.account.teamEntry.fetch(
+          pool.teamEntry
+        );
+        expect(teamEntryAccount.isClaimed).to.be.true;
+        const matchPoolAccount = await program.account.matchPool.fetch(
+          pool.matchPool
+        );
+        expect(matchPoolAccount.totalClaimed.toNumber()).to.equal(prize);
+      });
+
+      it("Rejects claiming a prize twice", async () => {
+        await expectError(claim(), "PrizeAlreadyClaimed");
+      });
+    });
+
+    describe("withdraw_deposit", () => {
+      let pool: Pool;
+
+      const withdraw = (entries: number) =>
+        program.methods
+          .withdrawDeposit(entries)
+          .accounts({
+            matchPool: pool.matchPool,
+            config,
+            poolTokenAccount: pool.poolTokenAccount,
+            tokenMint: usdcMint,
+            userDeposit: pool.userDeposit,
+            userTokenAccount: adminTokenAccount,
+            user,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+
+      before(async () => {
+        pool = await createPool("WITHDRAW", 60);
+        await enter(pool, 2);
+        await commitTeam(pool);
+      });
+
+      it("Rejects withdrawing an entry that backs a team", async () => {
+        await expectError(withdraw(2), "EntriesInUse");
+      });
+
+      it("Rejects closing the deposit record of a live pool", async () => {
+        await expectError(
+          program.methods
+            .closeUserDeposit()
+            .accounts({
+              matchPool: pool.matchPool,
+              config,
+              userDeposit: pool.userDeposit,
+              user,
+            })
+            .rpc(),
+          "InvalidMatchStatus"
+        );
+      });
+
+      it("Refunds unused entries", async () => {
+        const balanceBefore = await balanceOf(adminTokenAccount);
+
+        await withdraw(1);
+
+        expect(await balanceOf(adminTokenAccount)).to.equal(
+          balanceBefore + 10_000_000
+        );
+        const userDepositAccount = await program.account.userDeposit.fetch(
+          pool.userDeposit
+        );
+        expect(userDepositAccount.entries).to.equal(1);
+        expect(userDepositAccount.amount.toNumber()).to.equal(10_000_000);
+        const matchPoolAccount = await program.account.matchPool.fetch(
+          pool.matchPool
+        );
+        expect(matchPoolAccount.totalDeposited.toNumber()).to.equal(10_000_000);
+        expect(matchPoolAccount.participantCount).to.equal(1);
+      });
+    });
+
+    describe("raise_dispute and resolve_dispute", () => {
+      let pool: Pool;
+      let dispute: PublicKey;
+      let otherTokenAccount: PublicKey;
+
+      const setChallengePeriod = (seconds: number) =>
+        program.methods
+          .setChallengePeriod(new BN(seconds))
+          .accounts({ config, superAdmin: user })
+          .rpc();
+
+      const raiseDispute = () =>
+        program.methods
+          .raiseDispute(Array.from(randomBytes(32)))
+          .accounts({
+            matchPool: pool.matchPool,
+            config,
+            poolTokenAccount: pool.poolTokenAccount,
+            tokenMint: usdcMint,
+            userDeposit: pool.userDeposit,
+            dispute,
+            userTokenAccount: adminTokenAccount,
+            user,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+      const resolveDispute = (upheld: boolean, bondTokenAccount: PublicKey) =>
+        program.methods
+          .resolveDispute(upheld)
+          .accounts({
+            matchPool: pool.matchPool,
+            config,
+            dispute,
+            poolTokenAccount: pool.poolTokenAccount,
+            tokenMint: usdcMint,
+            bondTokenAccount,
+            disputer: user,
+            superAdmin: user,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+
+      const voidResults = () =>
+        program.methods
+          .voidResults()
+          .accounts({ matchPool: pool.matchPool, config, superAdmin: user })
+          .rpc();
+
+      before(async () => {
+        // A second account of the admin's that is not the treasury
+        otherTokenAccount = await createAccount(
+          provider.connection,
+          admin,
+          usdcMint,
+          user,
+          Keypair.generate()
+        );
+
+        pool = await createPool("DISPUTE", 15);
+        dispute = pda(
+          Buffer.from("dispute"),
+          Buffer.from(pool.id),
+          user.toBuffer()
+        );
+        await enter(pool, 1);
+        await playMatch(pool);
+        await allocatePrize(pool, 9_500_000);
+      });
+
+      after(async () => {
+        await setChallengePeriod(challengePeriod);
+      });
+
+      it("Rejects a challenge period of zero", async () => {
+        await expectError(setChallengePeriod(0), "InvalidChallengePeriod");
+      });
+
+      it("Rejects disputes before results are posted", async () => {
+        await expectError(raiseDispute(), "ResultsNotPosted");
+      });
+
+      it("Holds one entry fee as the dispute bond", async () => {
+        await setChallengePeriod(60);
+        await postResults(pool);
+        const balanceBefore = await balanceOf(adminTokenAccount);
+
+        await raiseDispute();
+
+        expect(await balanceOf(adminTokenAccount)).to.equal(
+          balanceBefore - 10_000_000
+        );
+        const disputeAccount = await program.account.dispute.fetch(dispute);
+        expect(disputeAccount.bond.toNumber()).to.equal(10_000_000);
+        const matchPoolAccount = await program.account.matchPool.fetch(
+          pool.matchPool
+        );
+        expect(matchPoolAccount.status).to.deep.equal({ disputed: {} });
+        expect(matchPoolAccount.openDisputes).to.equal(1);
+      });
+
+      it("Rejects forfeiting a bond anywhere but the treasury", async () => {
+        await expectError(
+          resolveDispute(false, otherTokenAccount),
+          "InvalidTreasury"
+        );
+      });
+
+      it("Returns the bond and voids the results of an upheld dispute", async () => {
+        const balanceBefore = await balanceOf(adminTokenAccount);
+
+        await resolveDispute(true, adminTokenAccount);
+
+        expect(await balanceOf(adminTokenAccount)).to.equal(
+          balanceBefore + 10_000_000
+        );
+        expect(await program.account.dispute.fetchNullable(dispute)).to.be.null;
+        const matchPoolAccount = await program.account.matchPool.fetch(
+          pool.matchPool
+        );
+        expect(matchPoolAccount.status).to.deep.equal({ scoring: {} });
+        expect(matchPoolAccount.resultsHash).to.deep.equal(Array(32).fill(0));
+      });
+
+      it("Rejects voiding results that were never posted", async () => {
+        await expectError(voidResults(), "ResultsNotPosted");
+      });
+
+      it("Lets the super-admin void posted results", async () => {
+        await postResults(pool);
+
+        await voidResults();
+
+        const matchPoolAccount = await program.account.matchPool.fetch(
+          pool.matchPool
+        );
+        expect(matchPoolAccount.resultsHash).to.deep.equal(Array(32).fill(0));
+        expect(matchPoolAccount.challengeEndTime.toNumber()).to.equal(0);
+      });
+    });
+
+    describe("sponsor_top_up and refund_sponsor", () => {
+      let pool: Pool;
+
+      const topUp = (amount: number) =>
+        program.methods
+          .sponsorTopUp(new BN(amount))
+          .accounts({
+            matchPool: pool.matchPool,
+            config,
+            poolTokenAccount: pool.poolTokenAccount,
+            tokenMint: usdcMint,
+            sponsorTokenAccount: adminTokenAccount,
+            sponsor: user,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+
+      const refundSponsor = () =>
+        program.methods
+          .refundSponsor()
+          .accounts({
+            matchPool: pool.matchPool,
+            config,
+            poolTokenAccount: pool.poolTokenAccount,
+            tokenMint: usdcMint,
+            sponsorTokenAccount: adminTokenAccount,
+            cranker: user,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+
+      before(async () => {
+        pool = await createPool("SPONSOR", 60);
+        await enter(pool, 1);
+      });
+
+      it("Rejects sponsor funds before a sponsor is named", async () => {
+        await expectError(topUp(20_000_000), "InvalidSponsor");
+      });
+
+      it("Takes sponsor funds from the named sponsor", async () => {
+        await program.methods
+          .setGuaranteedAmount(new BN(50_000_000), user)
+          .accounts({ matchPool: pool.matchPool, config, admin: user })
+          .rpc();
+
+        await topUp(20_000_000);
+
+        const matchPoolAccount = await program.account.matchPool.fetch(
+          pool.matchPool
+        );
+        expect(matchPoolAccount.sponsor.toString()).to.equal(user.toString());
+        expect(matchPoolAccount.sponsoredAmount.toNumber()).to.equal(
+          20_000_000
+        );
+        expect(await balanceOf(pool.poolTokenAccount)).to.equal(30_000_000);
+      });
+
+      it("Rejects sponsor refunds while the match is live", async () => {
+        await expectError(refundSponsor(), "InvalidMatchStatus");
+      });
+
+      it("Returns all sponsor funds from a cancelled match", async () => {
+        await cancelMatch(pool);
+        const balanceBefore = await balanceOf(adminTokenAccount);
+
+        await refundSponsor();
+
+        expect(await balanceOf(adminTokenAccount)).to.equal(
+          balanceBefore + 20_000_000
+        );
+        const matchPoolAccount = await program.account.matchPool.fetch(
+          pool.matchPool
+        );
+        expect(matchPoolAccount.sponsoredAmount.toNumber()).to.equal(0);
+      });
+    });
+
+    describe("deposit_sol", () => {
+      let pool: Pool;
+
+      const depositSol = (entries: number) =>
+        program.methods
+          .depositSol(entries)
+          .accounts({
+            matchPool: pool.matchPool,
+            config,
+            poolTokenAccount: pool.poolTokenAccount,
+            userDeposit: pool.userDeposit,
+            user,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+      before(async () => {
+        const nativeAllowedMint = pda(
+          Buffer.from("allowed_mint"),
+          NATIVE_MINT.toBuffer()
+        );
+        await program.methods
+          .setAllowedMint(new BN(1_000_000), new BN(100_000_000))
+          .accounts({
+            config,
+            allowedMint: nativeAllowedMint,
+            tokenMint: NATIVE_MINT,
+            superAdmin: user,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        // Fees of a SOL contest go to the treasury's wSOL account
+        const treasuryWsol = await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          admin,
+          NATIVE_MINT,
+          user
+        );
+
+        pool = await createPool("SOL", 60, {
+          mint: NATIVE_MINT,
+          mintAllowlist: nativeAllowedMint,
+          treasuryTokenAccount: treasuryWsol.address,
+        });
+      });
+
+      it("Wraps SOL entries into the pool", async () => {
+        await depositSol(2);
+
+        // 0.01 SOL per entry
+        expect(await balanceOf(pool.poolTokenAccount)).to.equal(20_000_000);
+        const userDepositAccount = await program.account.userDeposit.fetch(
+          pool.userDeposit
+        );
+        expect(userDepositAccount.entries).to.equal(2);
+        expect(userDepositAccount.amount.toNumber()).to.equal(20_000_000);
+        const matchPoolAccount = await program.account.matchPool.fetch(
+          pool.matchPool
+        );
+        expect(matchPoolAccount.totalDeposited.toNumber()).to.equal(20_000_000);
+      });
+
+      it("Rejects SOL entries beyond the per-user limit", async () => {
+        await expectError(depositSol(2), "EntryLimitExceeded");
+      });
+    });
+
+    describe("finalize_registration", () => {
+      let shortPool: Pool;
+      let fullPool: Pool;
+
+      const finalizeRegistration = (pool: Pool) =>
+        program.methods
+          .finalizeRegistration()
+          .accounts({ matchPool: pool.matchPool, config, cranker: user })
+          .rpc();
+
+      before(async () => {
+        shortPool = await createPool("SHORT", 10, {
+          rules: { ...entryRules, minParticipants: 2 },
+        });
+        fullPool = await createPool("FULL", 10);
+        await enter(shortPool, 1);
+        await enter(fullPool, 1);
+      });
+
+      it("Rejects finalizing while registration is open", async () => {
+        await expectError(
+          finalizeRegistration(shortPool),
+          "InvalidMatchStatus"
+        );
+      });
+
+      it("Cancels a contest short of its minimum headcount", async () => {
+        await waitUntil(shortPool.registrationEnd + 2);
+
+        await finalizeRegistration(shortPool);
+
+        const matchPoolAccount = await program.account.matchPool.fetch(
+          shortPool.matchPool
+        );
+        expect(matchPoolAccount.status).to.deep.equal({ cancelled: {} });
+      });
+
+      it("Locks a contest that reached its minimum headcount", async () => {
+        await finalizeRegistration(fullPool);
+
+        const matchPoolAccount = await program.account.matchPool.fetch(
+          fullPool.matchPool
+        );
+        expect(matchPoolAccount.status).to.deep.equal({ locked: {} });
+      });
+
+      it("Rejects finalizing a contest twice", async () => {
+        await expectError(finalizeRegistration(fullPool), "InvalidMatchStatus");
+      });
+    });
+
+    describe("rollover dust", () => {
+      let fromPool: Pool;
+      let toPool: Pool;
+
+      const finalizePrizeClaims = (sweepTokenAccount: PublicKey) =>
+        program.methods
+          .finalizePrizeClaims()
+          .accounts({
+            matchPool: fromPool.matchPool,
+            config,
+            poolTokenAccount: fromPool.poolTokenAccount,
+            tokenMint: usdcMint,
+            sweepTokenAccount,
+            treasuryTokenAccount: adminTokenAccount,
+            rolloverPool: toPool.matchPool,
+            admin: user,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+
+      before(async () => {
+        fromPool = await createPool("ROLLFROM", 15);
+        toPool = await createPool("ROLLTO", 600);
+        await enter(fromPool, 1);
+        await playMatch(fromPool);
+
+        await program.methods
+          .setPrizeTable([{ startRank: 1, endRank: 1, bps: 10_000 }], {
+            rollover: { 0: toPool.matchPool },
+          })
+          .accounts({ matchPool: fromPool.matchPool, config, admin: user })
+          .rpc();
+        // 0.5 USDC of the 9.5 USDC prize pool is left over
+        await allocatePrize(fromPool, 9_000_000);
+        await postResults(fromPool);
+
+        const { challengeEndTime } = await program.account.matchPool.fetch(
+          fromPool.matchPool
+        );
+        await waitUntil(challengeEndTime.toNumber() + 1);
+        await program.methods
+          .claimPrize(0)
+          .accounts({
+            matchPool: fromPool.matchPool,
+            config,
+            poolTokenAccount: fromPool.poolTokenAccount,
+            tokenMint: usdcMint,
+            teamEntry: fromPool.teamEntry,
+            winnerTokenAccount: adminTokenAccount,
+            winner: user,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+      });
+
+      it("Rejects sweeping dust anywhere but the receiving pool", async () => {
+        await expectError(
+          finalizePrizeClaims(adminTokenAccount),
+          "InvalidDustAccount"
+        );
+      });
+
+      it("Adds the dust to the receiving pool's prizes", async () => {
+        await finalizePrizeClaims(toPool.poolTokenAccount);
+
+        expect(await balanceOf(toPool.poolTokenAccount)).to.equal(500_000);
+        const toPoolAccount = await program.account.matchPool.fetch(
+          toPool.matchPool
+        );
+        expect(toPoolAccount.rolloverAmount.toNumber()).to.equal(500_000);
+        const fromPoolAccount = await program.account.matchPool.fetch(
+          fromPool.matchPool
+        );
+        expect(fromPoolAccount.status).to.deep.equal({ settled: {} });
+      });
+
+      it("Sweeps what a cancelled pool holds to the treasury", async () => {
+        await cancelMatch(toPool);
+        const balanceBefore = await balanceOf(adminTokenAccount);
+
+        await program.methods
+          .closeMatchPool()
+          .accounts({
+            matchPool: toPool.matchPool,
+            config,
+            poolTokenAccount: toPool.poolTokenAccount,
+            tokenMint: usdcMint,
+            treasuryTokenAccount: adminTokenAccount,
+            admin: user,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        expect(await balanceOf(adminTokenAccount)).to.equal(
+          balanceBefore + 500_000
+        );
+        const toPoolAccount = await program.account.matchPool.fetch(
+          toPool.matchPool
+        );
+        expect(toPoolAccount.status).to.deep.equal({ closed: {} });
+      });
+    });
+
+    describe("submit_scores, compute_team_score and rank_and_settle", () => {
+      let pool: Pool;
+      let matchScores: PublicKey;
+
+      const setOracle = (oracle: PublicKey) =>
+        program.methods
+          .setOracle(oracle)
+          .accounts({ config, superAdmin: user })
+          .rpc();
+
+      const submitScores = (
+        points: { playerId: number; points: number }[],
+        finalize: boolean
+      ) =>
+        program.methods
+          .submitScores(points, finalize)
+          .accounts({
+            matchPool: pool.matchPool,
+            config,
+            matchScores,
+            oracle: user,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+      const computeTeamScore = () =>
+        program.methods
+          .computeTeamScore(user, 0)
+          .accounts({
+            matchPool: pool.matchPool,
+            config,
+            matchScores,
+            teamEntry: pool.teamEntry,
+          })
+          .rpc();
+
+      const rankAndSettle = (teamEntries: PublicKey[]) =>
+        program.methods
+          .rankAndSettle()
+          .accounts({ matchPool: pool.matchPool, config, matchScores })
+          .remainingAccounts(
+            teamEntries.map((pubkey) => ({
+              pubkey,
+              isWritable: true,
+              isSigner: false,
+            }))
+          )
+          .rpc();
+
+      // 10 points for every player of the lineup
+      const points = lineup.players.map((playerId) => ({
+        playerId,
+        points: 10,
+      }));
+
+      before(async () => {
+        pool = await createPool("SCORED", 15);
+        matchScores = pda(Buffer.from("match_scores"), Buffer.from(pool.id));
+        await enter(pool, 1);
+        await playMatch(pool);
+        await setPrizeTable(pool);
+      });
+
+      it("Rejects points from anyone but the oracle", async () => {
+        await setOracle(Keypair.generate().publicKey);
+
+        await expectError(submitScores(points, false), "Unauthorized");
+      });
+
+      it("Rejects scoring a team before the points are final", async () => {
+        await setOracle(user);
+        await submitScores(points, false);
+
+        await expectError(computeTeamScore(), "ScoresNotFinal");
+      });
+
+      it("Scores a revealed team from the corrected final points", async () => {
+        // The captain's points are corrected as they are finalized
+        await submitScores([{ playerId: lineup.captain, points: 50 }], true);
+
+        await computeTeamScore();
+
+        // In half points: captain 50 x4, vice-captain 10 x3, nine players 10 x2
+        const teamEntryAccount = await program.account.teamEntry.fetch(
+          pool.teamEntry
+        );
+        expect(teamEntryAccount.isScored).to.be.true;
+        expect(teamEntryAccount.score.toNumber()).to.equal(410);
+        const matchPoolAccount = await program.account.matchPool.fetch(
+          pool.matchPool
+        );
+        expect(matchPoolAccount.scoredCount).to.equal(1);
+      });
+
+      it("Rejects points once they are final", async () => {
+        await expectError(submitScores(points, false), "ScoresFinalized");
+      });
+
+      it("Rejects scoring a team twice", async () => {
+        await expectError(computeTeamScore(), "TeamAlreadyScored");
+      });
+
+      it("Rejects a leaderboard missing revealed teams", async () => {
+        await expectError(rankAndSettle([]), "LeaderboardIncomplete");
+      });
+
+      it("Allocates prizes from the ranked leaderboard", async () => {
+        await rankAndSettle([pool.teamEntry]);
+
+        // 9.5 USDC, one entry after the 5% fee
+        const teamEntryAccount = await program.account.teamEntry.fetch(
+          pool.teamEntry
+        );
+        expect(teamEntryAccount.prizeAmount.toNumber()).to.equal(9_500_000);
+        const matchPoolAccount = await program.account.matchPool.fetch(
+          pool.matchPool
+        );
+        expect(matchPoolAccount.totalAllocated.toNumber()).to.equal(9_500_000);
+        expect(matchPoolAccount.leaderboardSettled).to.be.true;
+      });
+
+      it("Rejects manual allocations once the leaderboard is settled", async () => {
+        await expectError(allocatePrize(pool, 1_000_000), "LeaderboardSettled");
+      });
+    });
+
+    describe("finalize_with_merkle_root and claim_with_proof", () => {
+      let pool: Pool;
+      // 9.5 USDC prize pool, split between the user and another winner
+      const prizes = [
+        { user, amount: 9_000_000 },
+        { user: Keypair.generate().publicKey, amount: 500_000 },
+      ];
+      const tree = prizeTree(prizes);
+
+      const finalizeWithMerkleRoot = (totalAmount: number) =>
+        program.methods
+          .finalizeWithMerkleRoot(
+            tree.root,
+            new BN(totalAmount),
+            new BN(Math.floor(Date.now() / 1000) + 3600)
+          )
+          .accounts({ matchPool: pool.matchPool, config, admin: user })
+          .rpc();
+
+      const claimWithProof = (amount: number) =>
+        program.methods
+          .claimWithProof(new BN(amount), tree.proof(0))
+          .accounts({
+            matchPool: pool.matchPool,
+            config,
+            poolTokenAccount: pool.poolTokenAccount,
+            tokenMint: usdcMint,
+            claimReceipt: pda(
+              Buffer.from("claim_receipt"),
+              Buffer.from(pool.id),
+              user.toBuffer()
+            ),
+            winnerTokenAccount: adminTokenAccount,
+            winner: user,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+      before(async () => {
+        pool = await createPool("MERKLE", 15);
+        await enter(pool, 1);
+        await playMatch(pool);
+      });
+
+      it("Rejects prizes beyond the prize pool", async () => {
+        await expectError(
+          finalizeWithMerkleRoot(9_500_001),
+          "InsufficientPoolFunds"
+        );
+      });
+
+      it("Commits the root of the prize tree", async () => {
+        await finalizeWithMerkleRoot(9_500_000);
+
+        const matchPoolAccount = await program.account.matchPool.fetch(
+          pool.matchPool
+        );
+        expect(matchPoolAccount.merkleRoot).to.deep.equal(tree.root);
+        expect(matchPoolAccount.totalAllocated.toNumber()).to.equal(9_500_000);
+      });
+
+      it("Rejects claims before the results survive the challenge window", async () => {
+        await postResults(pool);
+
+        await expectError(claimWithProof(9_000_000), "PayoutsLocked");
+      });
+
+      it("Rejects a claim the tree does not hold", async () => {
+        const { challengeEndTime } = await program.account.matchPool.fetch(
+          pool.matchPool
+        );
+        await waitUntil(challengeEndTime.toNumber() + 1);
+
+        await expectError(claimWithProof(9_500_000), "InvalidMerkleProof");
+      });
+
+      it("Pays a prize proven against the root", async () => {
+        const balanceBefore = await balanceOf(adminTokenAccount);
+
+        await claimWithProof(9_000_000);
+
+        expect(await balanceOf(adminTokenAccount)).to.equal(
+          balanceBefore + 9_000_000
+        );
+        const matchPoolAccount = await program.account.matchPool.fetch(
+          pool.matchPool
+        );
+        expect(matchPoolAccount.totalClaimed.toNumber()).to.equal(9_000_000);
+      });
+
+      it("Rejects claiming the same prize twice", async () => {
+        try {
+          await claimWithProof(9_000_000);
+          expect.fail("A second claim should have been rejected");
+        } catch (error) {
+          // The claim receipt's `init` fails because the receipt exists
+          expect([String(error), ...(error.logs ?? [])].join("\n")).to.match(
+            /already in use/
+          );
+        }
+      });
+    });
+
+    describe("update_schedule", () => {
+      let pool: Pool;
+
+      const updateSchedule = (
+        target: Pool,
+        registrationEnd: number,
+        matchStart: number
+      ) =>
+        program.methods
+          .updateSchedule(new BN(registrationEnd), new BN(matchStart))
+          .accounts({ matchPool: target.matchPool, config, admin: user })
+          .rpc();
+
+      before(async () => {
+        pool = await createPool("SCHEDULE", 60);
+      });
+
+      it("Rejects moving the registration deadline forward", async () => {
+        await expectError(
+          updateSchedule(pool, pool.registrationEnd - 10, pool.registrationEnd),
+          "InvalidRegistrationEndTime"
+        );
+      });
+
+      it("Rejects a match start before the registration deadline", async () => {
+        await expectError(
+          updateSchedule(pool, pool.registrationEnd + 30, pool.registrationEnd),
+          "InvalidMatchStartTime"
+        );
+      });
+
+      it("Pushes the schedule back while registration is open", async () => {
+        await updateSchedule(
+          pool,
+          pool.registrationEnd + 30,
+          pool.registrationEnd + 90
+        );
+
+        const matchPoolAccount = await program.account.matchPool.fetch(
+          pool.matchPool
+        );
+        expect(matchPoolAccount.registrationEndTime.toNumber()).to.equal(
+          pool.registrationEnd + 30
+        );
+        expect(matchPoolAccount.matchStartTime.toNumber()).to.equal(
+          pool.registrationEnd + 90
+        );
+      });
+
+      it("Rejects schedule changes once registration has closed", async () => {
+        const closedPool = await createPool("SCHEDULE_CLOSED", 5);
+        await waitUntil(closedPool.registrationEnd + 2);
+
+        await expectError(
+          updateSchedule(
+            closedPool,
+            closedPool.registrationEnd + 60,
+            closedPool.registrationEnd + 120
+          ),
+          "InvalidMatchStatus"
+        );
+      });
+    });
+
+    describe("set_paused", () => {
+      let livePool: Pool;
+      let cancelledPool: Pool;
+
+      const setPaused = (
+        paused: boolean,
+        exitsWhenPaused: boolean,
+        guardian?: Keypair
+      ) =>
+        program.methods
+          .setPaused(paused, exitsWhenPaused)
+          .accounts({ config, guardian: guardian?.publicKey ?? user })
+          .signers(guardian ? [guardian] : [])
+          .rpc();
+
+      const refundDeposit = () =>
+        program.methods
+          .refundDeposit()
+          .accounts({
+            matchPool: cancelledPool.matchPool,
+            config,
+            poolTokenAccount: cancelledPool.poolTokenAccount,
+            tokenMint: usdcMint,
+            userDeposit: cancelledPool.userDeposit,
+            userTokenAccount: adminTokenAccount,
+            user,
+            cranker: user,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+
+      before(async () => {
+        livePool = await createPool("PAUSED_LIVE", 60);
+        cancelledPool = await createPool("PAUSED_CANCEL", 60);
+        await enter(cancelledPool, 1);
+        await cancelMatch(cancelledPool);
+      });
+
+      after(async () => {
+        await setPaused(false, false);
+      });
+
+      it("Rejects pausing by anyone but the guardian or super-admin", async () => {
+        await expectError(
+          setPaused(true, false, Keypair.generate()),
+          "Unauthorized"
+        );
+      });
+
+      it("Rejects entries into every pool while paused", async () => {
+        await setPaused(true, false);
+
+        await expectError(enter(livePool, 1), "ProgramPaused");
+      });
+
+      it("Keeps refunds closed while paused unless exits are allowed", async () => {
+        await expectError(refundDeposit(), "ProgramPaused");
+
+        await setPaused(true, true);
+        const balanceBefore = await balanceOf(adminTokenAccount);
+
+        await refundDeposit();
+
+        expect(await balanceOf(adminTokenAccount)).to.equal(
+          balanceBefore + 10_000_000
+        );
+        // Exits only, entries stay closed
+        await expectError(enter(livePool, 1), "ProgramPaused");
+      });
+
+      it("Takes entries again once unpaused", async () => {
+        await setPaused(false, false);
+
+        await enter(livePool, 1);
+
+        const userDepositAccount = await program.account.userDeposit.fetch(
+          livePool.userDeposit
+        );
+        expect(userDepositAccount.entries).to.equal(1);
+      });
+    });
+
+    describe("Token-2022 transfer fees", () => {
+      let pool: Pool;
+      let userFeeTokenAccount: PublicKey;
+      const feeMint = Keypair.generate();
+      const feeBasisPoints = 100; // The mint withholds 1% of every transfer
+
+      const enterWithFee = () =>
+        program.methods
+          .enterContest(1)
+          .accounts({
+            matchPool: pool.matchPool,
+            config,
+            poolTokenAccount: pool.poolTokenAccount,
+            tokenMint: feeMint.publicKey,
+            userDeposit: pool.userDeposit,
+            userTokenAccount: userFeeTokenAccount,
+            user,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+      before(async () => {
+        const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+        await provider.sendAndConfirm(
+          new Transaction().add(
+            SystemProgram.createAccount({
+              fromPubkey: user,
+              newAccountPubkey: feeMint.publicKey,
+              space: mintLen,
+              lamports:
+                await provider.connection.getMinimumBalanceForRentExemption(
+                  mintLen
+                ),
+              programId: TOKEN_2022_PROGRAM_ID,
+            }),
+            createInitializeTransferFeeConfigInstruction(
+              feeMint.publicKey,
+              user,
+              user,
+              feeBasisPoints,
+              BigInt(1_000_000_000),
+              TOKEN_2022_PROGRAM_ID
+            ),
+            createInitializeMintInstruction(
+              feeMint.publicKey,
+              6,
+              user,
+              null,
+              TOKEN_2022_PROGRAM_ID
+            )
+          ),
+          [feeMint]
+        );
+
+        userFeeTokenAccount = await createAssociatedTokenAccount(
+          provider.connection,
+          admin,
+          feeMint.publicKey,
+          user,
+          undefined,
+          TOKEN_2022_PROGRAM_ID
+        );
+        await mintTo(
+          provider.connection,
+          admin,
+          feeMint.publicKey,
+          userFeeTokenAccount,
+          user,
+          100_000_000,
+          [],
+          undefined,
+          TOKEN_2022_PROGRAM_ID
+        );
+
+        const feeAllowedMint = pda(
+          Buffer.from("allowed_mint"),
+          feeMint.publicKey.toBuffer()
+        );
+        await program.methods
+          .setAllowedMint(new BN(1_000_000), new BN(100_000_000))
+          .accounts({
+            config,
+            allowedMint: feeAllowedMint,
+            tokenMint: feeMint.publicKey,
+            superAdmin: user,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        pool = await createPool("FEE", 60, {
+          mint: feeMint.publicKey,
+          mintAllowlist: feeAllowedMint,
+          treasuryTokenAccount: userFeeTokenAccount,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        });
+      });
+
+      it("Credits entries with what the pool receives after the fee", async () => {
+        await enterWithFee();
+
+        // 10 tokens sent, 0.1 withheld by the mint
+        expect(await balanceOf(pool.poolTokenAccount)).to.equal(9_900_000);
+        const userDepositAccount = await program.account.userDeposit.fetch(
+          pool.userDeposit
+        );
+        expect(userDepositAccount.amount.toNumber()).to.equal(9_900_000);
+        const matchPoolAccount = await program.account.matchPool.fetch(
+          pool.matchPool
+        );
+        expect(matchPoolAccount.totalDeposited.toNumber()).to.equal(9_900_000);
+      });
+
+      it("Refunds what was credited, less the fee on the way out", async () => {
+        await cancelMatch(pool);
+        const balanceBefore = await balanceOf(userFeeTokenAccount);
+
+        await program.methods
+          .refundDeposit()
+          .accounts({
+            matchPool: pool.matchPool,
+            config,
+            poolTokenAccount: pool.poolTokenAccount,
+            tokenMint: feeMint.publicKey,
+            userDeposit: pool.userDeposit,
+            userTokenAccount: userFeeTokenAccount,
+            user,
+            cranker: user,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .rpc();
+
+        // 9.9 tokens sent back, 0.099 withheld by the mint
+        expect(await balanceOf(userFeeTokenAccount)).to.equal(
+          balanceBefore + 9_801_000
+        );
+        expect(await balanceOf(pool.poolTokenAccount)).to.equal(0);
+        const matchPoolAccount = await program.account.matchPool.fetch(
+          pool.matchPool
+        );
+        expect(matchPoolAccount.totalDeposited.toNumber()).to.equal(0);
+      });
+    });
+
+    describe("distribute_prizes in pages", () => {
+      let pool: Pool;
+      let winners: { user: PublicKey; teamIndex: number; rank: number }[];
+
+      // One winner per page
+      const distributePage = (start: number) =>
+        program.methods
+          .distributePrizes(start, [winners[start]], [
+            winnersTree(winners).proof(start),
+          ])
+          .accounts({
+            matchPool: pool.matchPool,
+            config,
+            poolTokenAccount: pool.poolTokenAccount,
+            tokenMint: usdcMint,
+            sweepTokenAccount: adminTokenAccount,
+            treasuryTokenAccount: adminTokenAccount,
+            rolloverPool: null,
+            admin: user,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts([
+            { pubkey: adminTokenAccount, isWritable: true, isSigner: false },
+            {
+              pubkey: teamEntryOf(pool, winners[start].teamIndex),
+              isWritable: true,
+              isSigner: false,
+            },
+          ])
+          .rpc();
+
+      before(async () => {
+        pool = await createPool("PAGED", 15);
+        await enter(pool, 2);
+        await playMatch(pool, 2);
+
+        // 1st 60%, 2nd 40% of the 19 USDC left after the 5% fee
+        await program.methods
+          .setPrizeTable(
+            [
+              { startRank: 1, endRank: 1, bps: 6_000 },
+              { startRank: 2, endRank: 2, bps: 4_000 },
+            ],
+            { treasury: {} }
+          )
+          .accounts({ matchPool: pool.matchPool, config, admin: user })
+          .rpc();
+
+        winners = [
+          { user, teamIndex: 1, rank: 1 },
+          { user, teamIndex: 0, rank: 2 },
+        ];
+        await program.methods
+          .postResults(winnersTree(winners).root, winners.length)
+          .accounts({ matchPool: pool.matchPool, config, admin: user })
+          .rpc();
+
+        const { challengeEndTime } = await program.account.matchPool.fetch(
+          pool.matchPool
+        );
+        await waitUntil(challengeEndTime.toNumber() + 1);
+      });
+
+      it("Pays the first page and keeps the pool in scoring", async () => {
+        await distributePage(0);
+
+        const matchPoolAccount = await program.account.matchPool.fetch(
+          pool.matchPool
+        );
+        expect(matchPoolAccount.status).to.deep.equal({ scoring: {} });
+        expect(matchPoolAccount.distributionCursor).to.equal(1);
+        expect(matchPoolAccount.distributedSoFar.toNumber()).to.equal(
+          11_400_000
+        );
+      });
+
+      it("Pays nothing twice when a page is replayed", async () => {
+        const balanceBefore = await balanceOf(pool.poolTokenAccount);
+
+        await distributePage(0);
+
+        expect(await balanceOf(pool.poolTokenAccount)).to.equal(balanceBefore);
+        const matchPoolAccount = await program.account.matchPool.fetch(
+          pool.matchPool
+        );
+        expect(matchPoolAccount.distributionCursor).to.equal(1);
+        expect(matchPoolAccount.distributedSoFar.toNumber()).to.equal(
+          11_400_000
+        );
+      });
+
+      it("Pays the last page and settles the pool", async () => {
+        await distributePage(1);
+
+        const matchPoolAccount = await program.account.matchPool.fetch(
+          pool.matchPool
+        );
+        expect(matchPoolAccount.status).to.deep.equal({ settled: {} });
+        expect(matchPoolAccount.distributionCursor).to.equal(2);
+        expect(matchPoolAccount.distributedSoFar.toNumber()).to.equal(
+          19_000_000
+        );
+        const secondTeam = await program.account.teamEntry.fetch(
+          teamEntryOf(pool, 0)
+        );
+        expect(secondTeam.prizeAmount.toNumber()).to.equal(7_600_000);
+        expect(await balanceOf(pool.poolTokenAccount)).to.equal(0);
+      });
+    });
+  });
 });